@@ -2,11 +2,15 @@ use crate::config::Config;
 use crate::controller::Controller;
 use crate::error::*;
 use crate::logger::CliLogger;
+use crate::pkg::Diff;
 
 #[derive(Debug)]
 pub enum Procedure {
     List,
     Install,
+    Diff,
+    Sync,
+    Remove,
     #[allow(dead_code)]
     Test,
 }
@@ -23,14 +27,16 @@ impl<'a> App<'a> {
         }
         #[cfg(debug_assertions)]
         debug!("config: {:?}", config);
-        let mut controller = Controller::new()?;
-        if let Some(program) = config.program {
-            controller.pkg.manager.configure_kind(program)?;
-        }
+        let controller = if config.all {
+            Controller::all()?
+        } else {
+            Controller::new(config.program)?
+        };
         let app = Self { controller, config };
         Ok(app)
     }
 
+    /// Run the procedure, returning whether it succeeded, e.g. not when `diff` finds drift
     pub fn init(&mut self) -> Result<bool> {
         match self.config.procedure {
             Procedure::List => {
@@ -45,13 +51,46 @@ impl<'a> App<'a> {
             }
             Procedure::Install => {
                 if let Some(input) = &self.config.input {
-                    self.controller.install(input)?;
+                    self.controller
+                        .install(input, self.config.dry_run, self.config.yes)?;
                     Ok(true)
                 } else {
                     error!("Missing input from install subcommand");
                     Ok(false)
                 }
             }
+            Procedure::Diff => match (&self.config.input, &self.config.output) {
+                (Some(input), Some(output)) => {
+                    let diffs = self.controller.diff(input)?;
+                    output.write_diff(&diffs, self.controller.all)?;
+                    Ok(!diffs.values().any(Diff::has_drift))
+                }
+                _ => Err("Missing input from diff subcommand".into()),
+            },
+            Procedure::Sync => {
+                if let Some(input) = &self.config.input {
+                    self.controller.sync(
+                        input,
+                        self.config.remove,
+                        self.config.dry_run,
+                        self.config.yes,
+                    )?;
+                    Ok(true)
+                } else {
+                    error!("Missing input from sync subcommand");
+                    Ok(false)
+                }
+            }
+            Procedure::Remove => {
+                if let Some(input) = &self.config.input {
+                    self.controller
+                        .remove(input, self.config.dry_run, self.config.yes)?;
+                    Ok(true)
+                } else {
+                    error!("Missing input from remove subcommand");
+                    Ok(false)
+                }
+            }
             Procedure::Test => Ok(true),
         }
     }
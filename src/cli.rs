@@ -1,4 +1,6 @@
 use crate::app::Procedure;
+use crate::cmd::{Package, PackageList};
+use crate::error::*;
 use crate::io::{Input, Output};
 use crate::logger::filter_level_occurences;
 use clap::{App, AppSettings, Arg, ArgMatches, Values};
@@ -42,9 +44,32 @@ impl Cli {
                     .short('p')
                     .long("program")
                     .takes_value(true)
-                    .possible_values(&["pacman"])
+                    .possible_values(&[
+                        "pacman", "apt", "dnf", "zypper", "apk", "xbps", "portage", "flatpak",
+                        "snap", "nix", "brew", "cargo", "pipx", "pip", "npm", "guix",
+                    ])
                     .about("Explicitly set which package manager to use"),
             )
+            .arg(
+                Arg::new("all")
+                    .short('a')
+                    .long("all")
+                    .conflicts_with("program")
+                    .about("Use every detected package manager, sectioned by manager"),
+            )
+            .arg(
+                Arg::new("dry-run")
+                    .short('n')
+                    .long("dry-run")
+                    .about("Print packages to install or remove and the commands to run"),
+            )
+            .arg(
+                Arg::new("yes")
+                    .short('y')
+                    .long("yes")
+                    .alias("noconfirm")
+                    .about("Install or remove packages without asking for confirmation"),
+            )
             .arg(
                 Arg::new("log")
                     .short('l')
@@ -73,6 +98,13 @@ impl Cli {
                             .takes_value(true)
                             .about("Filename to write package names"),
                     )
+                    .arg(
+                        Arg::new("format")
+                            .long("format")
+                            .takes_value(true)
+                            .possible_values(&["txt", "toml", "yaml", "json"])
+                            .about("Format of the output, instead of the output's file extension"),
+                    )
                     .arg(
                         Arg::new("force")
                             .short('f')
@@ -89,6 +121,44 @@ impl Cli {
                             .takes_value(true),
                     ),
             )
+            .subcommand(
+                App::new("diff")
+                    .about("Compare packages from a file with installed packages")
+                    .arg(
+                        Arg::new("input")
+                            .short('i')
+                            .long("input")
+                            .about("Packages to compare from a file")
+                            .required(true)
+                            .multiple(true)
+                            .takes_value(true),
+                    )
+                    .arg(
+                        Arg::new("format")
+                            .long("format")
+                            .takes_value(true)
+                            .possible_values(&["txt", "json"])
+                            .about("Format of the comparison"),
+                    ),
+            )
+            .subcommand(
+                App::new("sync")
+                    .about("Install and remove packages to match a file")
+                    .arg(
+                        Arg::new("input")
+                            .short('i')
+                            .long("input")
+                            .about("Packages to match from a file")
+                            .required(true)
+                            .multiple(true)
+                            .takes_value(true),
+                    )
+                    .arg(
+                        Arg::new("no-remove")
+                            .long("no-remove")
+                            .about("Keep installed packages which aren't in the file"),
+                    ),
+            )
             .subcommand(
                 App::new("install")
                     .alias("add")
@@ -111,6 +181,29 @@ impl Cli {
                             .multiple(true)
                             .takes_value(true),
                     ),
+            )
+            .subcommand(
+                App::new("remove")
+                    .alias("rm")
+                    .about("Remove packages from input or a file")
+                    .setting(AppSettings::ArgRequiredElseHelp)
+                    .arg(
+                        Arg::new("packages")
+                            .index(1)
+                            .about("Package names to remove")
+                            .multiple(true)
+                            .min_values(1)
+                            .conflicts_with("input"),
+                    )
+                    .arg(
+                        Arg::new("input")
+                            .short('i')
+                            .long("input")
+                            .about("Packages to read from a file")
+                            .conflicts_with("packages")
+                            .multiple(true)
+                            .takes_value(true),
+                    ),
             );
 
         let matches = app.get_matches();
@@ -125,93 +218,112 @@ impl Cli {
             Procedure::List
         } else if self.matches.is_present("install") {
             Procedure::Install
+        } else if self.matches.is_present("diff") {
+            Procedure::Diff
+        } else if self.matches.is_present("sync") {
+            Procedure::Sync
+        } else if self.matches.is_present("remove") {
+            Procedure::Remove
         } else {
             info!("Running 'list' subcommand by default");
             Procedure::List
         }
     }
 
-    /// Output filename
-    pub fn get_output(&self) -> Option<Output> {
+    /// Output filename and format
+    pub fn get_output(&self) -> Option<Output<'_>> {
         self.matches
             .subcommand_matches("list")
-            .map(|list_matches| Output::new(list_matches.value_of("output")))
+            .or_else(|| self.matches.subcommand_matches("diff"))
+            .map(|matches| {
+                let format = matches
+                    .value_of("format")
+                    .and_then(|format| format.parse().ok());
+                Output::new(matches.value_of("output"), format)
+            })
     }
 
     /// Collect input from multiple or single file input
     ///
-    /// Used for both install and list inputs
-    fn get_file_input(&self, filenames: Values) -> Option<Input> {
-        let filenames: Vec<String> = filenames.map(str::to_string).collect();
-        if let Some(first_filename) = filenames.first() {
-            let input = Input::from_file(first_filename.to_string()).ok();
-            let mut packages = Vec::new();
-            for s in &filenames {
-                let subinput = Input::from_file(s.to_string()).ok();
-                if let Some(mut subinput) = subinput {
-                    packages.append(&mut subinput.list);
-                    continue;
-                } else {
-                    error!("Unable to read file '{}'", s);
-                }
-            }
-            if let Some(mut first_input) = input.clone() {
-                first_input.append_list(packages);
-                Some(first_input)
-            } else {
-                error!("Unable to read file '{}'", first_filename);
-                input
+    /// Used for install, list and diff inputs
+    fn get_file_input(&self, filenames: Values) -> Result<Option<Input>> {
+        let mut filenames = filenames.map(str::to_string);
+        if let Some(first_filename) = filenames.next() {
+            let mut input = Input::from_file(first_filename)?;
+            for filename in filenames {
+                let subinput = Input::from_file(filename)?;
+                input.append_list(subinput.list);
+                input.append_sections(subinput.sections);
             }
+            Ok(Some(input))
         } else {
             warn!("No files received in input");
-            None
+            Ok(None)
         }
     }
 
     /// Input filename or packages to filter installed list
-    pub fn get_list_input(&self) -> Option<Input> {
+    pub fn get_list_input(&self) -> Result<Option<Input>> {
         if let Some(list_matches) = self.matches.subcommand_matches("list") {
             if let Some(filenames) = list_matches.values_of("input") {
                 self.get_file_input(filenames)
             } else {
-                None
+                Ok(None)
             }
         } else {
-            None
+            Ok(None)
+        }
+    }
+
+    /// Input filename of a subcommand
+    fn get_subcommand_file_input(&self, subcommand: &str) -> Result<Option<Input>> {
+        match self
+            .matches
+            .subcommand_matches(subcommand)
+            .and_then(|subcommand_matches| subcommand_matches.values_of("input"))
+        {
+            Some(filenames) => self.get_file_input(filenames),
+            None => Ok(None),
         }
     }
 
-    /// Input filename or packages
-    pub fn get_install_input(&self) -> Option<Input> {
-        if let Some(list_matches) = self.matches.subcommand_matches("install") {
+    /// Input filename to compare with installed packages
+    pub fn get_diff_input(&self) -> Result<Option<Input>> {
+        self.get_subcommand_file_input("diff")
+    }
+
+    /// Input filename for installed packages to match
+    pub fn get_sync_input(&self) -> Result<Option<Input>> {
+        self.get_subcommand_file_input("sync")
+    }
+
+    /// Input filename or packages to install
+    pub fn get_install_input(&self) -> Result<Option<Input>> {
+        self.get_packages_input("install", true)
+    }
+
+    /// Input filename or packages to remove
+    ///
+    /// Package names are never read as files, so that a file which happens to share a
+    /// package's name can't remove the packages it lists
+    pub fn get_remove_input(&self) -> Result<Option<Input>> {
+        self.get_packages_input("remove", false)
+    }
+
+    /// Input filename or packages of a subcommand
+    fn get_packages_input(&self, subcommand: &str, read_files: bool) -> Result<Option<Input>> {
+        if let Some(list_matches) = self.matches.subcommand_matches(subcommand) {
             if let Some(list) = list_matches.values_of("packages") {
-                let list: Vec<String> = list.map(str::to_string).collect();
-                let mut packages = Vec::new();
-                for s in &list {
-                    // Probably shouldn't let users cheat by allowing packages or filename input here...
-                    if Path::new(s).exists() {
-                        let subinput = Input::from_file(s.to_string()).ok();
-                        if let Some(mut subinput) = subinput {
-                            packages.append(&mut subinput.list);
-                            continue;
-                        }
-                    };
-                    packages.push(s.to_string());
-                }
-                if packages.is_empty() {
-                    warn!("No packages received in input");
-                }
-                let input = Input::from_list(packages);
-                Some(input)
+                read_packages_args(list, read_files).map(Some)
             } else if let Some(filenames) = list_matches.values_of("input") {
                 self.get_file_input(filenames)
             } else {
                 warn!("No files received in input");
-                None
+                Ok(None)
             }
         } else {
             warn!("No files received in input");
-            None
+            Ok(None)
         }
     }
 
@@ -251,8 +363,86 @@ impl Cli {
         }
     }
 
+    /// No remove
+    ///
+    /// bool whether sync should keep installed packages which aren't in the input
+    pub fn get_no_remove(&self) -> bool {
+        self.matches
+            .subcommand_matches("sync")
+            .is_some_and(|sync_matches| sync_matches.is_present("no-remove"))
+    }
+
+    /// Dry run
+    ///
+    /// bool whether packages should be left unchanged
+    pub fn get_dry_run(&self) -> bool {
+        self.matches.is_present("dry-run")
+    }
+
+    /// Yes
+    ///
+    /// bool whether package managers should continue without confirmation
+    pub fn get_yes(&self) -> bool {
+        self.matches.is_present("yes")
+    }
+
+    /// All
+    ///
+    /// bool whether every detected package manager should be used
+    pub fn get_all(&self) -> bool {
+        self.matches.is_present("all")
+    }
+
     /// Package manager program
     pub fn get_program(&self) -> Option<&str> {
         self.matches.value_of("program")
     }
 }
+
+/// Packages named by arguments
+///
+/// With `read_files`, an argument naming an existing file is read as a file instead
+fn read_packages_args<'a>(args: impl Iterator<Item = &'a str>, read_files: bool) -> Result<Input> {
+    let mut packages: PackageList = Vec::new();
+    let mut files: Vec<Input> = Vec::new();
+    for s in args {
+        // Probably shouldn't let users cheat by allowing packages or filename input here...
+        if read_files && Path::new(s).exists() {
+            let mut subinput = Input::from_file(s.to_string())?;
+            packages.append(&mut subinput.list);
+            files.push(subinput);
+            continue;
+        };
+        packages.push(Package::new(s));
+    }
+    let mut input = Input::from_list(packages);
+    for subinput in files {
+        input.append_sections(subinput.sections);
+    }
+    if input.list.is_empty() && input.sections.is_empty() {
+        warn!("No packages received in input");
+    }
+    Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::read_packages_args;
+
+    #[test]
+    fn reads_package_args_as_files_only_when_allowed() {
+        let args = ["ripgrep", "test/example.txt"];
+        let input = read_packages_args(args.iter().copied(), false).unwrap();
+        let names: Vec<&str> = input.list.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, args);
+        let input = read_packages_args(args.iter().copied(), true).unwrap();
+        assert!(!input.list.iter().any(|p| p.name == "test/example.txt"));
+        assert!(input.list.iter().any(|p| p.name == "ripgrep"));
+    }
+
+    #[test]
+    fn keeps_sections_of_package_args_read_as_files() {
+        let input = read_packages_args(["test/sections.txt"].iter().copied(), true).unwrap();
+        assert!(!input.sections.is_empty());
+    }
+}
@@ -0,0 +1,80 @@
+use super::{package_names, Installed, Operation, Package, PackageList, PackageManagerCmds};
+use crate::error::*;
+use std::fs;
+use std::process::Command;
+
+/// Apk for Alpine Linux
+///
+/// Docs: https://wiki.alpinelinux.org/wiki/Alpine_Package_Keeper
+#[derive(Debug)]
+pub struct ApkCmd {
+    program: &'static str,
+
+    /// Explicitly requested packages, including any version or repository pins
+    world: &'static str,
+}
+
+impl ApkCmd {
+    pub fn new() -> Self {
+        Self {
+            program: "apk",
+            world: "/etc/apk/world",
+        }
+    }
+}
+
+/// World file entries are separated by any whitespace
+fn parse_world(content: &str) -> Vec<String> {
+    content.split_whitespace().map(str::to_string).collect()
+}
+
+impl PackageManagerCmds for ApkCmd {
+    fn list_installed(&self) -> Result<Option<Installed>> {
+        let content = fs::read_to_string(self.world)?;
+        let world = parse_world(&content);
+        if world.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(world.into_iter().map(Package::new).collect()))
+        }
+    }
+
+    fn install_cmds(&self, package_list: PackageList, _yes: bool) -> Result<Vec<Command>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.arg("add");
+        cmd.args(package_names(&package_list));
+        Ok(vec![cmd])
+    }
+
+    /// Remove packages from the world file, apk also removes dependencies which
+    /// aren't needed otherwise
+    fn remove_cmds(&self, package_list: PackageList, _yes: bool) -> Result<Vec<Command>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.arg("del");
+        cmd.args(package_names(&package_list));
+        Ok(vec![cmd])
+    }
+
+    fn confirms(&self, _operation: Operation) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_world;
+
+    #[test]
+    fn parses_world_entries() {
+        let world = parse_world("alpine-base\nbash>5 git\nneovim@edge\n\n");
+        let expected: Vec<String> = vec![
+            "alpine-base".into(),
+            "bash>5".into(),
+            "git".into(),
+            "neovim@edge".into(),
+        ];
+        assert_eq!(world, expected);
+    }
+}
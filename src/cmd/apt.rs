@@ -0,0 +1,56 @@
+use super::output::Output;
+use super::{package_names, Installed, PackageList, PackageManagerCmds};
+use crate::error::*;
+use std::process::Command;
+
+/// APT for Debian and Ubuntu
+///
+/// Docs: https://wiki.debian.org/Apt
+#[derive(Debug)]
+pub struct AptCmd {
+    program: &'static str,
+
+    /// Program used to query packages marked as manually installed
+    mark_program: &'static str,
+}
+
+impl AptCmd {
+    pub fn new() -> Self {
+        Self {
+            program: "apt-get",
+            mark_program: "apt-mark",
+        }
+    }
+}
+
+impl PackageManagerCmds for AptCmd {
+    fn list_installed(&self) -> Result<Option<Installed>> {
+        let program = self.mark_program;
+        let mut cmd = Command::new(program);
+        cmd.arg("showmanual");
+        Output::new(&mut cmd, program).read_packages()
+    }
+
+    fn install_cmds(&self, package_list: PackageList, yes: bool) -> Result<Vec<Command>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.arg("install");
+        if yes {
+            cmd.arg("-y");
+        }
+        cmd.args(package_names(&package_list));
+        Ok(vec![cmd])
+    }
+
+    /// Remove packages along with dependencies which aren't needed otherwise
+    fn remove_cmds(&self, package_list: PackageList, yes: bool) -> Result<Vec<Command>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.args(["remove", "--auto-remove"]);
+        if yes {
+            cmd.arg("-y");
+        }
+        cmd.args(package_names(&package_list));
+        Ok(vec![cmd])
+    }
+}
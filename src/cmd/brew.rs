@@ -0,0 +1,84 @@
+use super::output::Output;
+use super::{package_names, Installed, Operation, PackageList, PackageManagerCmds};
+use crate::error::*;
+use std::process::Command;
+
+/// Homebrew on Linux
+///
+/// Formulae from third-party taps are kept by their full name, e.g. `user/repo/formula`,
+/// and their taps are added before installing
+///
+/// Docs: https://docs.brew.sh/Homebrew-on-Linux
+#[derive(Debug)]
+pub struct BrewCmd {
+    program: &'static str,
+}
+
+impl BrewCmd {
+    pub fn new() -> Self {
+        Self { program: "brew" }
+    }
+}
+
+/// Tap of a formula's full name
+fn get_tap(name: &str) -> Option<&str> {
+    let mut segments = name.rsplitn(2, '/');
+    match (segments.next(), segments.next()) {
+        (Some(_formula), Some(tap)) if tap.contains('/') => Some(tap),
+        _ => None,
+    }
+}
+
+impl PackageManagerCmds for BrewCmd {
+    fn list_installed(&self) -> Result<Option<Installed>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.arg("leaves");
+        Output::new(&mut cmd, program).read_packages()
+    }
+
+    fn install_cmds(&self, package_list: PackageList, _yes: bool) -> Result<Vec<Command>> {
+        let mut cmds: Vec<Command> = Vec::new();
+        let program = self.program;
+        let mut taps: Vec<&str> = package_list
+            .iter()
+            .filter_map(|package| get_tap(&package.name))
+            .collect();
+        taps.sort_unstable();
+        taps.dedup();
+        for tap in taps {
+            let mut cmd = Command::new(program);
+            cmd.args(["tap", tap]);
+            cmds.push(cmd);
+        }
+        let mut cmd = Command::new(program);
+        cmd.arg("install");
+        cmd.args(package_names(&package_list));
+        cmds.push(cmd);
+        Ok(cmds)
+    }
+
+    fn remove_cmds(&self, package_list: PackageList, _yes: bool) -> Result<Vec<Command>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.arg("uninstall");
+        cmd.args(package_names(&package_list));
+        Ok(vec![cmd])
+    }
+
+    fn confirms(&self, _operation: Operation) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get_tap;
+
+    #[test]
+    fn gets_tap_from_full_name() {
+        assert_eq!(get_tap("ripgrep"), None);
+        assert_eq!(get_tap("homebrew/core/ripgrep"), Some("homebrew/core"));
+        assert_eq!(get_tap("hashicorp/tap/terraform"), Some("hashicorp/tap"));
+    }
+}
@@ -0,0 +1,247 @@
+use super::output::Output;
+use super::{package_names, Installed, Operation, Package, PackageList, PackageManagerCmds};
+use crate::error::*;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Cargo for Rust binaries
+///
+/// Manifest lines keep the source and build flags of each crate, e.g.
+/// `ripgrep features=pcre2 locked` or `pkgls git=https://github.com/brettinternet/pkgls`
+///
+/// Docs: https://doc.rust-lang.org/cargo/commands/cargo-install.html
+#[derive(Debug)]
+pub struct CargoCmd {
+    program: &'static str,
+}
+
+impl CargoCmd {
+    pub fn new() -> Self {
+        Self { program: "cargo" }
+    }
+}
+
+/// Attributes passed to `cargo install` as flags of the same name
+const INSTALL_FLAGS: [&str; 10] = [
+    "version",
+    "git",
+    "branch",
+    "tag",
+    "rev",
+    "path",
+    "features",
+    "all-features",
+    "no-default-features",
+    "locked",
+];
+
+/// Tracking file cargo writes for each installed crate
+#[derive(Debug, Deserialize)]
+struct Crates2 {
+    installs: BTreeMap<String, CrateInstall>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CrateInstall {
+    #[serde(default)]
+    features: Vec<String>,
+
+    #[serde(default)]
+    all_features: bool,
+
+    #[serde(default)]
+    no_default_features: bool,
+}
+
+fn get_crates2_path() -> Option<PathBuf> {
+    env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cargo")))
+        .map(|cargo_home| cargo_home.join(".crates2.json"))
+}
+
+/// Parse a `name version (source)` key and the flags it was installed with
+fn parse_install(key: &str, install: &CrateInstall) -> Option<Package> {
+    let mut words = key.splitn(3, ' ');
+    let mut package = Package::new(words.next()?);
+    let source = words
+        .nth(1)
+        .map(|source| source.trim_start_matches('(').trim_end_matches(')'));
+    if let Some(git) = source.and_then(|source| source.strip_prefix("git+")) {
+        // The fragment is the locked commit and the query holds the branch or tag
+        let git = git.split('#').next().unwrap_or(git);
+        let (url, query) = git.split_once('?').unwrap_or((git, ""));
+        package = package.with_attribute("git", url);
+        for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+            package = package.with_attribute(key, value);
+        }
+    } else if let Some(path) = source.and_then(|source| source.strip_prefix("path+file://")) {
+        package = package.with_attribute("path", path);
+    }
+    if !install.features.is_empty() {
+        package = package.with_attribute("features", install.features.join(","));
+    }
+    if install.all_features {
+        package = package.with_attribute("all-features", "");
+    }
+    if install.no_default_features {
+        package = package.with_attribute("no-default-features", "");
+    }
+    Some(package)
+}
+
+fn parse_crates2(content: &str) -> Result<Installed> {
+    let crates2: Crates2 = serde_json::from_str(content)?;
+    Ok(crates2
+        .installs
+        .iter()
+        .filter_map(|(key, install)| parse_install(key, install))
+        .collect())
+}
+
+/// Crates are listed as unindented `name version:` lines followed by their binaries
+fn parse_install_list(lines: &[String]) -> Installed {
+    lines
+        .iter()
+        .filter(|line| line.ends_with(':'))
+        .filter_map(|line| line.split_whitespace().next())
+        .map(Package::new)
+        .collect()
+}
+
+impl PackageManagerCmds for CargoCmd {
+    fn list_installed(&self) -> Result<Option<Installed>> {
+        if let Some(content) = get_crates2_path().and_then(|path| fs::read_to_string(path).ok()) {
+            let installed = parse_crates2(&content)?;
+            return Ok(if installed.is_empty() {
+                None
+            } else {
+                Some(installed)
+            });
+        }
+        debug!("Unable to read .crates2.json, falling back to listing installed crates");
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.args(["install", "--list"]);
+        let lines = Output::new(&mut cmd, program).read_lines()?;
+        Ok(lines.map(|lines| parse_install_list(&lines)))
+    }
+
+    /// Crates from the registry without flags are installed together, while the rest
+    /// are installed one at a time
+    fn install_cmds(&self, package_list: PackageList, _yes: bool) -> Result<Vec<Command>> {
+        let mut cmds: Vec<Command> = Vec::new();
+        let program = self.program;
+        let (plain, attributed): (PackageList, PackageList) =
+            package_list.into_iter().partition(|package| {
+                !package
+                    .attributes
+                    .keys()
+                    .any(|key| INSTALL_FLAGS.contains(&key.as_str()))
+            });
+        if !plain.is_empty() {
+            let mut cmd = Command::new(program);
+            cmd.arg("install");
+            cmd.args(package_names(&plain));
+            cmds.push(cmd);
+        }
+        for package in attributed {
+            let mut cmd = Command::new(program);
+            cmd.arg("install");
+            let flags = package
+                .attributes
+                .iter()
+                .filter(|(key, _)| INSTALL_FLAGS.contains(&key.as_str()));
+            for (key, value) in flags {
+                if value.is_empty() {
+                    cmd.arg(format!("--{}", key));
+                } else {
+                    cmd.arg(format!("--{}", key)).arg(value);
+                }
+            }
+            cmd.arg(&package.name);
+            cmds.push(cmd);
+        }
+        Ok(cmds)
+    }
+
+    fn install_attributes(&self) -> &'static [&'static str] {
+        &INSTALL_FLAGS
+    }
+
+    fn remove_cmds(&self, package_list: PackageList, _yes: bool) -> Result<Vec<Command>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.arg("uninstall");
+        cmd.args(package_names(&package_list));
+        Ok(vec![cmd])
+    }
+
+    fn confirms(&self, _operation: Operation) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_crates2, parse_install_list, CargoCmd};
+    use crate::cmd::{format_cmd, Package, PackageManagerCmds};
+
+    #[test]
+    fn parses_crates2_sources_and_flags() {
+        let content = r#"{"installs":{
+            "ripgrep 13.0.0 (registry+https://github.com/rust-lang/crates.io-index)":{"version_req":null,"bins":["rg"],"features":["pcre2"],"all_features":false,"no_default_features":false,"profile":"release"},
+            "pkgls 0.2.0 (git+https://github.com/brettinternet/pkgls?branch=main#26f9d88)":{"bins":["pkgls"],"features":[],"all_features":false,"no_default_features":true}
+        }}"#;
+        let installed: Vec<String> = parse_crates2(content)
+            .unwrap()
+            .iter()
+            .map(|package| package.to_string())
+            .collect();
+        let expected: Vec<String> = vec![
+            "pkgls branch=main git=https://github.com/brettinternet/pkgls no-default-features"
+                .into(),
+            "ripgrep features=pcre2".into(),
+        ];
+        assert_eq!(installed, expected);
+    }
+
+    #[test]
+    fn parses_install_list_crate_names() {
+        let lines: Vec<String> = vec![
+            "bat v0.18.1:".into(),
+            "bat".into(),
+            "ripgrep v13.0.0:".into(),
+            "rg".into(),
+        ];
+        let installed: Vec<String> = parse_install_list(&lines)
+            .into_iter()
+            .map(|package| package.name)
+            .collect();
+        assert_eq!(installed, vec!["bat".to_string(), "ripgrep".to_string()]);
+    }
+
+    #[test]
+    fn passes_only_cargo_flags_to_install() {
+        let packages = vec![
+            Package::new("bat").with_attribute("note", "cat replacement"),
+            Package::new("ripgrep")
+                .with_attribute("version", "13.0.0")
+                .with_attribute("locked", "")
+                .with_attribute("reason", "explicit"),
+        ];
+        let cmds = CargoCmd::new().install_cmds(packages, false).unwrap();
+        let cmd_lines: Vec<String> = cmds.iter().map(format_cmd).collect();
+        assert_eq!(
+            cmd_lines,
+            vec![
+                "cargo install bat",
+                "cargo install --locked --version 13.0.0 ripgrep",
+            ]
+        );
+    }
+}
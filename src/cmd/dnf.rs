@@ -0,0 +1,56 @@
+use super::output::Output;
+use super::{package_names, Installed, PackageList, PackageManagerCmds};
+use crate::error::*;
+use std::process::Command;
+
+/// DNF for Fedora and RHEL
+///
+/// Docs: https://dnf.readthedocs.io/en/latest/command_ref.html
+#[derive(Debug)]
+pub struct DnfCmd {
+    program: &'static str,
+}
+
+impl DnfCmd {
+    pub fn new() -> Self {
+        Self { program: "dnf" }
+    }
+}
+
+impl PackageManagerCmds for DnfCmd {
+    fn list_installed(&self) -> Result<Option<Installed>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.args(["repoquery", "--userinstalled", "--queryformat", "%{name}\n"]);
+        let installed = Output::new(&mut cmd, program).read_packages()?;
+        // Multilib packages are listed once per architecture
+        Ok(installed.map(|mut list| {
+            list.sort();
+            list.dedup();
+            list
+        }))
+    }
+
+    fn install_cmds(&self, package_list: PackageList, yes: bool) -> Result<Vec<Command>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.arg("install");
+        if yes {
+            cmd.arg("--assumeyes");
+        }
+        cmd.args(package_names(&package_list));
+        Ok(vec![cmd])
+    }
+
+    /// Remove packages, dnf also removes dependencies which aren't needed otherwise
+    fn remove_cmds(&self, package_list: PackageList, yes: bool) -> Result<Vec<Command>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.arg("remove");
+        if yes {
+            cmd.arg("--assumeyes");
+        }
+        cmd.args(package_names(&package_list));
+        Ok(vec![cmd])
+    }
+}
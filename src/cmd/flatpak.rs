@@ -0,0 +1,150 @@
+use super::output::Output;
+use super::{Installed, Package, PackageList, PackageManagerCmds};
+use crate::error::*;
+use std::collections::BTreeMap;
+use std::process::Command;
+
+/// Flatpak for desktop applications
+///
+/// Manifest lines keep the remote and installation scope of each application,
+/// e.g. `org.mozilla.firefox remote=flathub scope=user`
+///
+/// Docs: https://docs.flatpak.org/en/latest/flatpak-command-reference.html
+#[derive(Debug)]
+pub struct FlatpakCmd {
+    program: &'static str,
+}
+
+impl FlatpakCmd {
+    pub fn new() -> Self {
+        Self { program: "flatpak" }
+    }
+}
+
+/// Parse a row of `application`, `origin` and `installation` columns
+fn parse_app(line: &str) -> Option<Package> {
+    let mut columns = line.split_whitespace();
+    let package = Package::new(columns.next()?);
+    let package = match columns.next() {
+        Some(remote) => package.with_attribute("remote", remote),
+        None => package,
+    };
+    let package = match columns.next() {
+        Some(scope) => package.with_attribute("scope", scope),
+        None => package,
+    };
+    Some(package)
+}
+
+/// Flag to select an installation scope
+fn scope_arg(scope: &str) -> String {
+    match scope {
+        "user" | "system" => format!("--{}", scope),
+        installation => format!("--installation={}", installation),
+    }
+}
+
+impl PackageManagerCmds for FlatpakCmd {
+    fn list_installed(&self) -> Result<Option<Installed>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.args(["list", "--app", "--columns=application,origin,installation"]);
+        let lines = Output::new(&mut cmd, program).read_lines()?;
+        Ok(lines.map(|lines| lines.iter().filter_map(|line| parse_app(line)).collect()))
+    }
+
+    /// Install applications with a single command per scope and remote
+    fn install_cmds(&self, package_list: PackageList, yes: bool) -> Result<Vec<Command>> {
+        let mut cmds: Vec<Command> = Vec::new();
+        let mut groups: BTreeMap<(Option<&str>, Option<&str>), Vec<&str>> = BTreeMap::new();
+        for package in &package_list {
+            groups
+                .entry((package.attribute("scope"), package.attribute("remote")))
+                .or_default()
+                .push(&package.name);
+        }
+        let program = self.program;
+        for ((scope, remote), apps) in groups {
+            let mut cmd = Command::new(program);
+            cmd.arg("install");
+            if yes {
+                cmd.arg("--assumeyes");
+            }
+            if let Some(scope) = scope {
+                cmd.arg(scope_arg(scope));
+            }
+            if let Some(remote) = remote {
+                cmd.arg(remote);
+            }
+            cmd.args(apps);
+            cmds.push(cmd);
+        }
+        Ok(cmds)
+    }
+
+    fn install_attributes(&self) -> &'static [&'static str] {
+        &["remote", "scope"]
+    }
+
+    fn remove_cmds(&self, package_list: PackageList, yes: bool) -> Result<Vec<Command>> {
+        let mut cmds: Vec<Command> = Vec::new();
+        let mut groups: BTreeMap<Option<&str>, Vec<&str>> = BTreeMap::new();
+        for package in &package_list {
+            groups
+                .entry(package.attribute("scope"))
+                .or_default()
+                .push(&package.name);
+        }
+        let program = self.program;
+        for (scope, apps) in groups {
+            let mut cmd = Command::new(program);
+            cmd.arg("uninstall");
+            if yes {
+                cmd.arg("--assumeyes");
+            }
+            if let Some(scope) = scope {
+                cmd.arg(scope_arg(scope));
+            }
+            cmd.args(apps);
+            cmds.push(cmd);
+        }
+        Ok(cmds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_app, FlatpakCmd};
+    use crate::cmd::{format_cmd, Package, PackageManagerCmds};
+
+    #[test]
+    fn parses_app_columns_into_attributes() {
+        let package = parse_app("org.mozilla.firefox\tflathub\tuser").unwrap();
+        assert_eq!(
+            package.to_string(),
+            "org.mozilla.firefox remote=flathub scope=user"
+        );
+    }
+
+    #[test]
+    fn groups_install_cmds_by_installation_and_remote() {
+        let packages = vec![
+            Package::new("org.mozilla.firefox")
+                .with_attribute("remote", "flathub")
+                .with_attribute("scope", "user"),
+            Package::new("org.gimp.GIMP")
+                .with_attribute("remote", "flathub")
+                .with_attribute("scope", "user"),
+            Package::new("com.spotify.Client"),
+        ];
+        let cmds = FlatpakCmd::new().install_cmds(packages, true).unwrap();
+        let cmd_lines: Vec<String> = cmds.iter().map(format_cmd).collect();
+        assert_eq!(
+            cmd_lines,
+            vec![
+                "flatpak install --assumeyes com.spotify.Client",
+                "flatpak install --assumeyes --user flathub org.mozilla.firefox org.gimp.GIMP",
+            ]
+        );
+    }
+}
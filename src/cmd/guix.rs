@@ -0,0 +1,81 @@
+use super::output::Output;
+use super::{package_names, Installed, Operation, Package, PackageList, PackageManagerCmds};
+use crate::error::*;
+use std::process::Command;
+
+/// Guix user profiles
+///
+/// Packages installed from an output other than the default `out` keep the
+/// output in their name, e.g. `glib:bin`
+///
+/// Docs: https://guix.gnu.org/manual/en/html_node/Invoking-guix-package.html
+#[derive(Debug)]
+pub struct GuixCmd {
+    program: &'static str,
+}
+
+impl GuixCmd {
+    pub fn new() -> Self {
+        Self { program: "guix" }
+    }
+}
+
+/// Parse a row of name, version, output and store path
+fn parse_installed(line: &str) -> Option<Package> {
+    let mut columns = line.split_whitespace();
+    match (columns.next(), columns.next(), columns.next()) {
+        (Some(name), Some(_version), Some("out")) => Some(Package::new(name)),
+        (Some(name), Some(_version), Some(output)) => {
+            Some(Package::new(format!("{}:{}", name, output)))
+        }
+        _ => None,
+    }
+}
+
+impl PackageManagerCmds for GuixCmd {
+    fn list_installed(&self) -> Result<Option<Installed>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.args(["package", "--list-installed"]);
+        let lines = Output::new(&mut cmd, program).read_lines()?;
+        Ok(lines.map(|lines| {
+            lines
+                .iter()
+                .filter_map(|line| parse_installed(line))
+                .collect()
+        }))
+    }
+
+    fn install_cmds(&self, package_list: PackageList, _yes: bool) -> Result<Vec<Command>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.arg("install");
+        cmd.args(package_names(&package_list));
+        Ok(vec![cmd])
+    }
+
+    fn remove_cmds(&self, package_list: PackageList, _yes: bool) -> Result<Vec<Command>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.arg("remove");
+        cmd.args(package_names(&package_list));
+        Ok(vec![cmd])
+    }
+
+    fn confirms(&self, _operation: Operation) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_installed;
+
+    #[test]
+    fn parses_installed_outputs() {
+        let hello = parse_installed("hello\t2.12.1\tout\t/gnu/store/3m0b5p4n-hello-2.12.1");
+        assert_eq!(hello.unwrap().name, "hello");
+        let glib = parse_installed("glib\t2.70.2\tbin\t/gnu/store/h4f9z2ab-glib-2.70.2-bin");
+        assert_eq!(glib.unwrap().name, "glib:bin");
+    }
+}
@@ -1,26 +1,221 @@
-use crate::error::Result;
+use crate::error::*;
+use output::Output;
+use std::collections::BTreeMap;
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::process::Command;
+use std::str::FromStr;
 
+pub mod apk;
+pub mod apt;
+pub mod brew;
+pub mod cargo;
+pub mod dnf;
+pub mod flatpak;
+pub mod guix;
+pub mod nix;
+pub mod npm;
 mod output;
 pub mod pacman;
+pub mod pip;
+pub mod pipx;
+pub mod portage;
+pub mod snap;
+pub mod xbps;
+pub mod zypper;
 
-pub type Installed = Vec<String>;
-pub type PackageList = Vec<String>;
+pub type Installed = Vec<Package>;
+pub type PackageList = Vec<Package>;
 
-pub trait PackageManagerCmds {
-    fn list_installed(&self) -> Result<Option<Installed>>;
+/// Installed version and install reason of a package
+///
+/// Details are reported for other tooling, but never kept as attributes, so that an
+/// installed version doesn't become a version to install
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Detail {
+    pub version: Option<String>,
+
+    /// Why the package was installed, e.g. `explicit`
+    pub reason: Option<String>,
+}
+
+/// Details of installed packages by name
+pub type Details = BTreeMap<String, Detail>;
+
+/// Package name and the attributes kept alongside it in a manifest
+///
+/// Attributes follow the name in a manifest line as `key=value` pairs or bare flags,
+/// e.g. `org.mozilla.firefox remote=flathub scope=user`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Package {
+    pub name: String,
+
+    /// Backend specific attributes, flags have an empty value
+    pub attributes: BTreeMap<String, String>,
+}
+
+impl Package {
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            attributes: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_attribute<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.attributes.insert(key.into(), value.into());
+        self
+    }
 
-    fn install(&self, package_list: PackageList) -> Result<()>;
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .get(key)
+            .map(String::as_str)
+            .filter(|value| !value.is_empty())
+    }
+}
+
+impl FromStr for Package {
+    type Err = Error;
+    fn from_str(line: &str) -> Result<Self> {
+        let mut words = line.split_whitespace();
+        let name = words
+            .next()
+            .ok_or_else(|| Error::from(format!("Missing package name in '{}'", line)))?;
+        let package = words.fold(Package::new(name), |package, word| {
+            match word.split_once('=') {
+                Some((key, value)) => package.with_attribute(key, value),
+                None => package.with_attribute(word, ""),
+            }
+        });
+        Ok(package)
+    }
 }
 
-impl Debug for dyn PackageManagerCmds {
-    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        write!(f, "{:?}", self)
+impl Display for Package {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.name)?;
+        for (key, value) in &self.attributes {
+            if value.is_empty() {
+                write!(f, " {}", key)?;
+            } else {
+                write!(f, " {}={}", key, value)?;
+            }
+        }
+        Ok(())
     }
 }
 
-impl Display for dyn PackageManagerCmds {
-    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        write!(f, "{:?}", self)
+/// Change a package manager makes to the installed packages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Install,
+    Remove,
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            Operation::Install => write!(f, "install"),
+            Operation::Remove => write!(f, "remove"),
+        }
+    }
+}
+
+/// Names of packages to pass as program arguments
+pub fn package_names(package_list: &[Package]) -> Vec<String> {
+    package_list.iter().map(|p| p.name.clone()).collect()
+}
+
+/// Run interactive commands in order, stopping at the first failure
+pub fn run_cmds(cmds: Vec<Command>) -> Result<()> {
+    for mut cmd in cmds {
+        let program = cmd.get_program().to_string_lossy().into_owned();
+        Output::new(&mut cmd, &program).interact()?;
+    }
+    Ok(())
+}
+
+/// Command line as it would be typed in a shell, quoting arguments where needed
+pub fn format_cmd(cmd: &Command) -> String {
+    std::iter::once(cmd.get_program())
+        .chain(cmd.get_args())
+        .map(|arg| {
+            let arg = arg.to_string_lossy();
+            if !arg.is_empty()
+                && arg
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "-_./:=@+,%#".contains(c))
+            {
+                arg.into_owned()
+            } else {
+                format!("'{}'", arg.replace('\'', "'\\''"))
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+pub trait PackageManagerCmds: Debug {
+    fn list_installed(&self) -> Result<Option<Installed>>;
+
+    /// Details of installed packages, where the manager reports them
+    fn list_details(&self) -> Result<Details> {
+        Ok(Details::new())
+    }
+
+    /// Commands to install packages, run in order, without confirmation when `yes` is set
+    fn install_cmds(&self, package_list: PackageList, yes: bool) -> Result<Vec<Command>>;
+
+    /// Commands to remove packages, run in order, without confirmation when `yes` is set
+    fn remove_cmds(&self, package_list: PackageList, yes: bool) -> Result<Vec<Command>>;
+
+    /// Package attributes applied when installing, others are ignored
+    fn install_attributes(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Whether the commands for an operation ask for confirmation unless `yes` is set
+    fn confirms(&self, _operation: Operation) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_cmd, Package};
+    use std::process::Command;
+
+    #[test]
+    fn package_parses_and_displays_attributes() {
+        let line = "org.mozilla.firefox remote=flathub scope=user";
+        let package: Package = line.parse().unwrap();
+        assert_eq!(package.name, "org.mozilla.firefox");
+        assert_eq!(package.attribute("remote"), Some("flathub"));
+        assert_eq!(package.attribute("scope"), Some("user"));
+        assert_eq!(package.to_string(), line);
+    }
+
+    #[test]
+    fn package_parses_flags() {
+        let package: Package = "code classic".parse().unwrap();
+        assert!(package.attributes.contains_key("classic"));
+        assert_eq!(package.attribute("classic"), None);
+        assert_eq!(package.to_string(), "code classic");
+    }
+
+    #[test]
+    fn formats_cmd_with_quoted_arguments() {
+        let mut cmd = Command::new("flatpak");
+        cmd.args([
+            "install",
+            "--user",
+            "my remote",
+            "it's",
+            "org.mozilla.firefox",
+        ]);
+        assert_eq!(
+            format_cmd(&cmd),
+            "flatpak install --user 'my remote' 'it'\\''s' org.mozilla.firefox"
+        );
     }
 }
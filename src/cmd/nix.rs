@@ -0,0 +1,237 @@
+use super::output::Output;
+use super::{package_names, Installed, Operation, Package, PackageList, PackageManagerCmds};
+use crate::error::*;
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+/// Nix user profiles
+///
+/// Profiles managed by `nix profile` keep the flake of each package, e.g.
+/// `hello flake=flake:nixpkgs`, while legacy `nix-env` profiles are listed by
+/// attribute path, e.g. `nixpkgs.hello`
+///
+/// Docs: https://nixos.org/manual/nix/stable/command-ref/new-cli/nix3-profile.html
+#[derive(Debug)]
+pub struct NixCmd {
+    program: &'static str,
+
+    /// Program for legacy profiles
+    env_program: &'static str,
+
+    /// Whether the user's profile is managed by `nix profile`
+    profile: bool,
+}
+
+impl NixCmd {
+    pub fn new() -> Self {
+        Self {
+            program: "nix",
+            env_program: "nix-env",
+            profile: uses_profile(),
+        }
+    }
+}
+
+/// New style profiles have a manifest.json
+fn uses_profile() -> bool {
+    env::var_os("HOME")
+        .map(|home| Path::new(&home).join(".nix-profile/manifest.json").exists())
+        .unwrap_or(false)
+}
+
+/// Drop the `legacyPackages.<system>.` or `packages.<system>.` prefix of a flake attribute
+fn strip_system(attr: &str) -> &str {
+    if attr.starts_with("legacyPackages.") || attr.starts_with("packages.") {
+        attr.splitn(3, '.').nth(2).unwrap_or(attr)
+    } else {
+        attr
+    }
+}
+
+/// Legacy profiles uninstall by derivation name rather than attribute path, which is
+/// usually the last attribute segment
+fn get_env_name(attr_path: &str) -> &str {
+    attr_path.rsplit('.').next().unwrap_or(attr_path)
+}
+
+/// Name the package after the last attribute segment unless a name is listed
+fn profile_package(name: Option<&str>, flake: &str, attr: &str) -> Package {
+    let attr = strip_system(attr);
+    let name = name.unwrap_or_else(|| attr.rsplit('.').next().unwrap_or(attr));
+    let package = Package::new(name).with_attribute("flake", flake);
+    if attr != name {
+        package.with_attribute("attr", attr)
+    } else {
+        package
+    }
+}
+
+/// Parse a row of index, flake reference, locked reference and store paths
+fn parse_profile_row(line: &str) -> Option<Package> {
+    let columns: Vec<&str> = line.split_whitespace().collect();
+    match columns.as_slice() {
+        [index, reference, ..] if index.parse::<u32>().is_ok() => {
+            let (flake, attr) = reference.split_once('#')?;
+            Some(profile_package(None, flake, attr))
+        }
+        _ => None,
+    }
+}
+
+/// Parse `nix profile list` output
+///
+/// Newer versions print a block of `Key: value` lines per package, while older versions
+/// print a single row per package
+fn parse_profile_list(lines: &[String]) -> Installed {
+    let mut packages = Vec::new();
+    let mut name: Option<&str> = None;
+    let mut attr: Option<&str> = None;
+    let mut flake: Option<&str> = None;
+    for line in lines {
+        match line.split_once(':').map(|(key, value)| (key, value.trim())) {
+            Some(("Name", value)) => name = Some(value),
+            Some(("Flake attribute", value)) => attr = Some(value),
+            Some(("Original flake URL", value)) => flake = Some(value),
+            _ => {
+                if let Some(package) = parse_profile_row(line) {
+                    packages.push(package);
+                }
+            }
+        }
+        if let (Some(attr_value), Some(flake_value)) = (attr, flake) {
+            packages.push(profile_package(name, flake_value, attr_value));
+            name = None;
+            attr = None;
+            flake = None;
+        }
+    }
+    packages
+}
+
+impl PackageManagerCmds for NixCmd {
+    fn list_installed(&self) -> Result<Option<Installed>> {
+        if self.profile {
+            let program = self.program;
+            let mut cmd = Command::new(program);
+            cmd.args(["profile", "list"]);
+            let lines = Output::new(&mut cmd, program).read_lines()?;
+            Ok(lines.map(|lines| parse_profile_list(&lines)))
+        } else {
+            let program = self.env_program;
+            let mut cmd = Command::new(program);
+            cmd.args(["--query", "--attr-path", "--no-name"]);
+            Output::new(&mut cmd, program).read_packages()
+        }
+    }
+
+    fn install_cmds(&self, package_list: PackageList, _yes: bool) -> Result<Vec<Command>> {
+        let mut cmds: Vec<Command> = Vec::new();
+        if self.profile {
+            let program = self.program;
+            let mut cmd = Command::new(program);
+            cmd.args(["profile", "install"]);
+            cmd.args(package_list.iter().map(|package| {
+                format!(
+                    "{}#{}",
+                    package.attribute("flake").unwrap_or("nixpkgs"),
+                    package.attribute("attr").unwrap_or(&package.name)
+                )
+            }));
+            cmds.push(cmd);
+        } else {
+            let program = self.env_program;
+            let mut cmd = Command::new(program);
+            cmd.arg("--install").arg("--attr");
+            cmd.args(package_names(&package_list));
+            cmds.push(cmd);
+        }
+        Ok(cmds)
+    }
+
+    fn install_attributes(&self) -> &'static [&'static str] {
+        &["flake", "attr"]
+    }
+
+    fn remove_cmds(&self, package_list: PackageList, _yes: bool) -> Result<Vec<Command>> {
+        let mut cmds: Vec<Command> = Vec::new();
+        if self.profile {
+            let program = self.program;
+            let mut cmd = Command::new(program);
+            cmd.args(["profile", "remove"]);
+            cmd.args(package_names(&package_list));
+            cmds.push(cmd);
+        } else {
+            let program = self.env_program;
+            let mut cmd = Command::new(program);
+            cmd.arg("--uninstall");
+            cmd.args(
+                package_list
+                    .iter()
+                    .map(|package| get_env_name(&package.name)),
+            );
+            cmds.push(cmd);
+        }
+        Ok(cmds)
+    }
+
+    fn confirms(&self, _operation: Operation) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_env_name, parse_profile_list};
+
+    fn to_strings(installed: super::Installed) -> Vec<String> {
+        installed
+            .iter()
+            .map(|package| package.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn parses_profile_list_blocks() {
+        let lines: Vec<String> = vec![
+            "Name:               hello".into(),
+            "Flake attribute:    legacyPackages.x86_64-linux.hello".into(),
+            "Original flake URL: flake:nixpkgs".into(),
+            "Locked flake URL:   github:NixOS/nixpkgs/4a3fc4cf".into(),
+            "Store paths:        /nix/store/qkzb2x0n-hello-2.12.1".into(),
+            "Name:               ripgrep-all".into(),
+            "Flake attribute:    legacyPackages.x86_64-linux.ripgrep-all".into(),
+            "Original flake URL: github:NixOS/nixpkgs/nixos-unstable".into(),
+            "Locked flake URL:   github:NixOS/nixpkgs/4a3fc4cf".into(),
+            "Store paths:        /nix/store/lx1y6pwm-ripgrep-all-0.9.6".into(),
+        ];
+        assert_eq!(
+            to_strings(parse_profile_list(&lines)),
+            vec![
+                "hello flake=flake:nixpkgs".to_string(),
+                "ripgrep-all flake=github:NixOS/nixpkgs/nixos-unstable".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_legacy_profile_list_rows() {
+        let lines: Vec<String> = vec![
+            "0 flake:nixpkgs#legacyPackages.x86_64-linux.hello github:NixOS/nixpkgs/4a3fc4cf#legacyPackages.x86_64-linux.hello /nix/store/qkzb2x0n-hello-2.12.1".into(),
+        ];
+        assert_eq!(
+            to_strings(parse_profile_list(&lines)),
+            vec!["hello flake=flake:nixpkgs".to_string()]
+        );
+    }
+
+    #[test]
+    fn uninstalls_legacy_packages_by_name() {
+        assert_eq!(get_env_name("nixpkgs.ripgrep"), "ripgrep");
+        assert_eq!(
+            get_env_name("nixpkgs.nodePackages.typescript"),
+            "typescript"
+        );
+        assert_eq!(get_env_name("hello"), "hello");
+    }
+}
@@ -0,0 +1,102 @@
+use super::output::Output;
+use super::{package_names, Installed, Operation, Package, PackageList, PackageManagerCmds};
+use crate::error::*;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::process::Command;
+
+/// Npm for globally installed Node.js packages
+///
+/// Docs: https://docs.npmjs.com/cli/commands/npm-ls
+#[derive(Debug)]
+pub struct NpmCmd {
+    program: &'static str,
+}
+
+impl NpmCmd {
+    pub fn new() -> Self {
+        Self { program: "npm" }
+    }
+}
+
+/// Packages bundled with Node.js rather than installed by the user
+const BUNDLED: [&str; 2] = ["npm", "corepack"];
+
+#[derive(Debug, Deserialize)]
+struct NpmList {
+    #[serde(default)]
+    dependencies: BTreeMap<String, serde_json::Value>,
+}
+
+fn parse_list(content: &str) -> Result<Installed> {
+    let list: NpmList = serde_json::from_str(content)?;
+    Ok(list
+        .dependencies
+        .into_keys()
+        .filter(|name| !BUNDLED.contains(&name.as_str()))
+        .map(Package::new)
+        .collect())
+}
+
+impl PackageManagerCmds for NpmCmd {
+    fn list_installed(&self) -> Result<Option<Installed>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.args(["ls", "--global", "--depth=0", "--json"]);
+        match Output::new(&mut cmd, program).read_lines()? {
+            Some(lines) => {
+                let installed = parse_list(&lines.join("\n"))?;
+                Ok(if installed.is_empty() {
+                    None
+                } else {
+                    Some(installed)
+                })
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn install_cmds(&self, package_list: PackageList, _yes: bool) -> Result<Vec<Command>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.args(["install", "--global"]);
+        cmd.args(package_names(&package_list));
+        Ok(vec![cmd])
+    }
+
+    fn remove_cmds(&self, package_list: PackageList, _yes: bool) -> Result<Vec<Command>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.args(["uninstall", "--global"]);
+        cmd.args(package_names(&package_list));
+        Ok(vec![cmd])
+    }
+
+    fn confirms(&self, _operation: Operation) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_list;
+
+    #[test]
+    fn parses_top_level_global_packages() {
+        let content = r#"{"name":"lib","dependencies":{
+            "@angular/cli":{"version":"13.3.3"},
+            "corepack":{"version":"0.10.0"},
+            "npm":{"version":"8.5.5"},
+            "typescript":{"version":"4.6.3"}
+        }}"#;
+        let installed: Vec<String> = parse_list(content)
+            .unwrap()
+            .into_iter()
+            .map(|package| package.name)
+            .collect();
+        assert_eq!(
+            installed,
+            vec!["@angular/cli".to_string(), "typescript".to_string()]
+        );
+    }
+}
@@ -1,14 +1,15 @@
+use super::{Installed, Package};
 use crate::error::*;
 use std::io::{BufRead, BufReader};
 use std::process::{ChildStderr, ChildStdout, Command, Stdio};
 
 pub struct Output<'a> {
     cmd: &'a mut Command,
-    program: &'static str,
+    program: &'a str,
 }
 
 impl<'a> Output<'a> {
-    pub fn new(cmd: &'a mut Command, program: &'static str) -> Self {
+    pub fn new(cmd: &'a mut Command, program: &'a str) -> Self {
         Self { cmd, program }
     }
 
@@ -25,12 +26,18 @@ impl<'a> Output<'a> {
                 self.program,
                 out.status.code().unwrap_or_default()
             );
-            use std::io::{Error, ErrorKind};
-            Err(Error::new(ErrorKind::Other, out.status.to_string()))
+            Err(std::io::Error::other(out.status.to_string()))
         }
     }
 
-    pub fn read_packages(&'a mut self) -> Result<Option<Vec<String>>> {
+    /// Packages named by each line of output
+    pub fn read_packages(&'a mut self) -> Result<Option<Installed>> {
+        let lines = self.read_lines()?;
+        Ok(lines.map(|lines| lines.into_iter().map(Package::new).collect()))
+    }
+
+    /// Non-empty lines of output for backends that parse the package names
+    pub fn read_lines(&'a mut self) -> Result<Option<Vec<String>>> {
         self.cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
 
         // Wait for https://github.com/rust-lang/rust/issues/44434
@@ -102,6 +109,7 @@ impl<'a> Output<'a> {
 #[cfg(test)]
 mod tests {
     use super::Output;
+    use crate::cmd::Package;
     use std::process::Command;
 
     #[test]
@@ -109,10 +117,10 @@ mod tests {
         let program = "printf";
         let mut cmd = Command::new(program);
         let packages = ["a", "b"];
-        cmd.arg(&packages.join("\n"));
+        cmd.arg(packages.join("\n"));
         let output = Output::new(&mut cmd, program).read_packages();
         assert!(output.is_ok());
-        let expected_output: Vec<String> = packages.iter().map(|s| s.to_string()).collect();
+        let expected_output: Vec<Package> = packages.iter().map(|s| Package::new(*s)).collect();
         assert_eq!(
             output.unwrap(),
             Some(expected_output),
@@ -1,22 +1,71 @@
 use super::output::Output;
-use super::{Installed, PackageList, PackageManagerCmds};
+use super::{package_names, Detail, Details, Installed, PackageList, PackageManagerCmds};
 use crate::error::*;
+use std::collections::HashSet;
 use std::process::Command;
 
 /// Pacman for Arch Linux
 ///
+/// Foreign packages, such as those from the AUR, are installed with an AUR helper
+/// when one is available. Packages are removed along with their configuration and
+/// unneeded dependencies unless other options are set in `PKGLS_PACMAN_REMOVE`
+///
 /// Docs: https://wiki.archlinux.org/title/Pacman
 #[derive(Debug)]
 pub struct PacmanCmd {
     program: &'static str,
+
+    /// AUR helper with a pacman compatible interface, e.g. paru or yay
+    aur_helper: Option<&'static str>,
+
+    /// Options for removing packages, e.g. `-Rns`
+    remove_args: Vec<String>,
 }
 
 impl PacmanCmd {
-    pub fn new() -> Self {
-        Self { program: "pacman" }
+    pub fn new(aur_helper: Option<&'static str>, remove_args: Vec<String>) -> Self {
+        Self {
+            program: "pacman",
+            aur_helper,
+            remove_args,
+        }
+    }
+
+    /// Package and group names available from the sync databases
+    fn list_sync(&self) -> Result<HashSet<String>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.arg("-Slq");
+        let mut sync: HashSet<String> = Output::new(&mut cmd, program)
+            .read_lines()?
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        let mut cmd = Command::new(program);
+        cmd.arg("-Sg");
+        let groups = Output::new(&mut cmd, program).read_lines()?;
+        sync.extend(
+            groups
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|line| line.split_whitespace().next())
+                .map(str::to_string),
+        );
+        Ok(sync)
     }
 }
 
+/// Name and version from a `name version` line of `pacman -Qe`
+fn parse_detail(line: &str) -> Option<(String, Detail)> {
+    let mut words = line.split_whitespace();
+    let name = words.next()?;
+    let detail = Detail {
+        version: words.next().map(str::to_string),
+        reason: Some("explicit".to_string()),
+    };
+    Some((name.to_string(), detail))
+}
+
 impl PackageManagerCmds for PacmanCmd {
     fn list_installed(&self) -> Result<Option<Installed>> {
         let program = self.program;
@@ -25,12 +74,84 @@ impl PackageManagerCmds for PacmanCmd {
         Output::new(&mut cmd, program).read_packages()
     }
 
-    fn install(&self, package_list: PackageList) -> Result<()> {
+    /// Versions of the explicitly installed packages
+    fn list_details(&self) -> Result<Details> {
         let program = self.program;
         let mut cmd = Command::new(program);
-        cmd.arg("-Syu");
-        cmd.args(&package_list);
-        Output::new(&mut cmd, program).interact()?;
-        Ok(())
+        cmd.arg("-Qe");
+        let lines = Output::new(&mut cmd, program).read_lines()?;
+        Ok(lines
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|line| parse_detail(line))
+            .collect())
+    }
+
+    /// Install packages found in the sync databases with pacman and the remaining
+    /// foreign packages with the AUR helper
+    ///
+    /// Without an AUR helper the foreign packages are skipped with a warning, and are
+    /// reported as failed once the repository packages are installed
+    fn install_cmds(&self, package_list: PackageList, yes: bool) -> Result<Vec<Command>> {
+        let mut cmds: Vec<Command> = Vec::new();
+        let sync = self.list_sync()?;
+        let (repo, foreign): (PackageList, PackageList) = package_list
+            .into_iter()
+            .partition(|package| sync.contains(&package.name));
+        if !repo.is_empty() {
+            let program = self.program;
+            let mut cmd = Command::new(program);
+            cmd.arg("-Syu");
+            if yes {
+                cmd.arg("--noconfirm");
+            }
+            cmd.args(package_names(&repo));
+            cmds.push(cmd);
+        }
+        if !foreign.is_empty() {
+            let names = package_names(&foreign);
+            match self.aur_helper {
+                Some(program) => {
+                    debug!("Installing foreign packages with {}", program);
+                    let mut cmd = Command::new(program);
+                    cmd.arg("-S");
+                    if yes {
+                        cmd.arg("--noconfirm");
+                    }
+                    cmd.args(names);
+                    cmds.push(cmd);
+                }
+                None => warn!(
+                    "No AUR helper available to install foreign packages: {}",
+                    names.join(" ")
+                ),
+            }
+        }
+        Ok(cmds)
+    }
+
+    fn remove_cmds(&self, package_list: PackageList, yes: bool) -> Result<Vec<Command>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.args(&self.remove_args);
+        if yes {
+            cmd.arg("--noconfirm");
+        }
+        cmd.args(package_names(&package_list));
+        Ok(vec![cmd])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_detail;
+
+    #[test]
+    fn parses_explicit_package_versions() {
+        let (name, detail) = parse_detail("bat 0.18.3-1").unwrap();
+        assert_eq!(name, "bat");
+        assert_eq!(detail.version.as_deref(), Some("0.18.3-1"));
+        assert_eq!(detail.reason.as_deref(), Some("explicit"));
+        assert!(parse_detail("").is_none());
     }
 }
@@ -0,0 +1,88 @@
+use super::output::Output;
+use super::{package_names, Installed, Operation, Package, PackageList, PackageManagerCmds};
+use crate::error::*;
+use std::process::Command;
+
+/// Pip for packages in the Python user site
+///
+/// Docs: https://pip.pypa.io/en/stable/user_guide/#user-installs
+#[derive(Debug)]
+pub struct PipUserCmd {
+    program: &'static str,
+}
+
+impl PipUserCmd {
+    pub fn new() -> Self {
+        Self { program: "pip" }
+    }
+}
+
+/// Strip the version from a `name==version` requirement
+fn parse_requirement(line: &str) -> Option<Package> {
+    let name = line.split("==").next()?.trim();
+    if name.is_empty() || name.starts_with('#') {
+        None
+    } else {
+        Some(Package::new(name))
+    }
+}
+
+impl PackageManagerCmds for PipUserCmd {
+    /// Packages that no other package requires approximate the explicitly requested set
+    fn list_installed(&self) -> Result<Option<Installed>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.args(["list", "--user", "--not-required", "--format=freeze"]);
+        let lines = Output::new(&mut cmd, program).read_lines()?;
+        Ok(lines.map(|lines| {
+            lines
+                .iter()
+                .filter_map(|line| parse_requirement(line))
+                .collect()
+        }))
+    }
+
+    fn install_cmds(&self, package_list: PackageList, _yes: bool) -> Result<Vec<Command>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.args(["install", "--user"]);
+        cmd.args(package_names(&package_list));
+        Ok(vec![cmd])
+    }
+
+    fn remove_cmds(&self, package_list: PackageList, yes: bool) -> Result<Vec<Command>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.arg("uninstall");
+        if yes {
+            cmd.arg("--yes");
+        }
+        cmd.args(package_names(&package_list));
+        Ok(vec![cmd])
+    }
+
+    /// Only `pip uninstall` asks before changing packages
+    fn confirms(&self, operation: Operation) -> bool {
+        operation == Operation::Remove
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_requirement, PipUserCmd};
+    use crate::cmd::{Operation, PackageManagerCmds};
+
+    #[test]
+    fn parses_requirement_names() {
+        let package = parse_requirement("requests==2.27.1").unwrap();
+        assert_eq!(package.name, "requests");
+        assert!(parse_requirement("").is_none());
+    }
+
+    #[test]
+    fn confirms_only_removal() {
+        let pip = PipUserCmd::new();
+        assert!(!pip.confirms(Operation::Install));
+        assert!(pip.confirms(Operation::Remove));
+    }
+}
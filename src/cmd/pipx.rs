@@ -0,0 +1,131 @@
+use super::output::Output;
+use super::{Installed, Operation, Package, PackageList, PackageManagerCmds};
+use crate::error::*;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::process::Command;
+
+/// Pipx for Python applications
+///
+/// Applications installed from something other than their package name keep the
+/// spec, e.g. `black spec=black==22.3.0`
+///
+/// Docs: https://pypa.github.io/pipx/docs/
+#[derive(Debug)]
+pub struct PipxCmd {
+    program: &'static str,
+}
+
+impl PipxCmd {
+    pub fn new() -> Self {
+        Self { program: "pipx" }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PipxList {
+    venvs: BTreeMap<String, Venv>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Venv {
+    metadata: VenvMetadata,
+}
+
+#[derive(Debug, Deserialize)]
+struct VenvMetadata {
+    main_package: MainPackage,
+}
+
+#[derive(Debug, Deserialize)]
+struct MainPackage {
+    package: String,
+    package_or_url: String,
+}
+
+fn parse_list(content: &str) -> Result<Installed> {
+    let list: PipxList = serde_json::from_str(content)?;
+    Ok(list
+        .venvs
+        .into_values()
+        .map(|venv| {
+            let main = venv.metadata.main_package;
+            if main.package_or_url != main.package {
+                Package::new(main.package).with_attribute("spec", main.package_or_url)
+            } else {
+                Package::new(main.package)
+            }
+        })
+        .collect())
+}
+
+impl PackageManagerCmds for PipxCmd {
+    fn list_installed(&self) -> Result<Option<Installed>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.args(["list", "--json"]);
+        match Output::new(&mut cmd, program).read_lines()? {
+            Some(lines) => {
+                let installed = parse_list(&lines.join("\n"))?;
+                Ok(if installed.is_empty() {
+                    None
+                } else {
+                    Some(installed)
+                })
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn install_cmds(&self, package_list: PackageList, _yes: bool) -> Result<Vec<Command>> {
+        let mut cmds: Vec<Command> = Vec::new();
+        let program = self.program;
+        for package in &package_list {
+            let mut cmd = Command::new(program);
+            cmd.arg("install");
+            cmd.arg(package.attribute("spec").unwrap_or(&package.name));
+            cmds.push(cmd);
+        }
+        Ok(cmds)
+    }
+
+    fn install_attributes(&self) -> &'static [&'static str] {
+        &["spec"]
+    }
+
+    fn remove_cmds(&self, package_list: PackageList, _yes: bool) -> Result<Vec<Command>> {
+        let mut cmds: Vec<Command> = Vec::new();
+        let program = self.program;
+        for package in &package_list {
+            let mut cmd = Command::new(program);
+            cmd.arg("uninstall");
+            cmd.arg(&package.name);
+            cmds.push(cmd);
+        }
+        Ok(cmds)
+    }
+
+    fn confirms(&self, _operation: Operation) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_list;
+
+    #[test]
+    fn parses_main_packages_from_json() {
+        let content = r#"{"pipx_spec_version":"0.1","venvs":{
+            "black":{"metadata":{"main_package":{"package":"black","package_or_url":"black==22.3.0","package_version":"22.3.0"},"python_version":"Python 3.10.4"}},
+            "httpie":{"metadata":{"main_package":{"package":"httpie","package_or_url":"httpie","package_version":"3.2.1"},"python_version":"Python 3.10.4"}}
+        }}"#;
+        let installed: Vec<String> = parse_list(content)
+            .unwrap()
+            .iter()
+            .map(|package| package.to_string())
+            .collect();
+        let expected: Vec<String> = vec!["black spec=black==22.3.0".into(), "httpie".into()];
+        assert_eq!(installed, expected);
+    }
+}
@@ -0,0 +1,64 @@
+use super::{package_names, Installed, Package, PackageList, PackageManagerCmds};
+use crate::error::*;
+use std::fs;
+use std::process::Command;
+
+/// Portage for Gentoo
+///
+/// Docs: https://wiki.gentoo.org/wiki/Portage
+#[derive(Debug)]
+pub struct PortageCmd {
+    program: &'static str,
+
+    /// Selected set of `category/name[:slot]` atoms
+    world: &'static str,
+}
+
+impl PortageCmd {
+    pub fn new() -> Self {
+        Self {
+            program: "emerge",
+            world: "/var/lib/portage/world",
+        }
+    }
+}
+
+impl PackageManagerCmds for PortageCmd {
+    fn list_installed(&self) -> Result<Option<Installed>> {
+        let content = fs::read_to_string(self.world)?;
+        let world: Installed = content
+            .lines()
+            .map(str::trim)
+            .filter(|atom| !atom.is_empty())
+            .map(Package::new)
+            .collect();
+        if world.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(world))
+        }
+    }
+
+    fn install_cmds(&self, package_list: PackageList, yes: bool) -> Result<Vec<Command>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        if !yes {
+            cmd.arg("--ask");
+        }
+        cmd.arg("--noreplace");
+        cmd.args(package_names(&package_list));
+        Ok(vec![cmd])
+    }
+
+    /// Remove packages along with dependencies which aren't needed otherwise
+    fn remove_cmds(&self, package_list: PackageList, yes: bool) -> Result<Vec<Command>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        if !yes {
+            cmd.arg("--ask");
+        }
+        cmd.arg("--depclean");
+        cmd.args(package_names(&package_list));
+        Ok(vec![cmd])
+    }
+}
@@ -0,0 +1,130 @@
+use super::output::Output;
+use super::{Installed, Operation, Package, PackageList, PackageManagerCmds};
+use crate::error::*;
+use std::process::Command;
+
+/// Snap for Ubuntu
+///
+/// Manifest lines keep the tracked channel and classic confinement of each snap,
+/// e.g. `code channel=latest/stable classic`
+///
+/// Docs: https://snapcraft.io/docs/getting-started
+#[derive(Debug)]
+pub struct SnapCmd {
+    program: &'static str,
+}
+
+impl SnapCmd {
+    pub fn new() -> Self {
+        Self { program: "snap" }
+    }
+}
+
+/// Parse a row of the `snap list` table
+///
+/// Bases, core snaps and snapd are provided for other snaps rather than requested
+fn parse_snap(line: &str) -> Option<Package> {
+    let columns: Vec<&str> = line.split_whitespace().collect();
+    let (name, tracking, notes) = match columns.as_slice() {
+        [name, _version, _rev, tracking, _publisher, notes, ..] => (*name, *tracking, *notes),
+        _ => return None,
+    };
+    let notes: Vec<&str> = notes.split(',').collect();
+    if name == "Name" || name == "snapd" || notes.contains(&"base") || notes.contains(&"core") {
+        return None;
+    }
+    let package = Package::new(name);
+    let package = if tracking != "-" {
+        package.with_attribute("channel", tracking)
+    } else {
+        package
+    };
+    let package = if notes.contains(&"classic") {
+        package.with_attribute("classic", "")
+    } else {
+        package
+    };
+    Some(package)
+}
+
+impl PackageManagerCmds for SnapCmd {
+    fn list_installed(&self) -> Result<Option<Installed>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.arg("list");
+        let lines = Output::new(&mut cmd, program).read_lines()?;
+        Ok(lines.map(|lines| lines.iter().filter_map(|line| parse_snap(line)).collect()))
+    }
+
+    /// Snaps without attributes are installed together, while channel and confinement
+    /// flags only apply to a single snap at a time
+    fn install_cmds(&self, package_list: PackageList, _yes: bool) -> Result<Vec<Command>> {
+        let mut cmds: Vec<Command> = Vec::new();
+        let program = self.program;
+        let (plain, attributed): (PackageList, PackageList) = package_list
+            .into_iter()
+            .partition(|package| package.attributes.is_empty());
+        if !plain.is_empty() {
+            let mut cmd = Command::new(program);
+            cmd.arg("install");
+            cmd.args(plain.iter().map(|package| &package.name));
+            cmds.push(cmd);
+        }
+        for package in attributed {
+            let mut cmd = Command::new(program);
+            cmd.arg("install");
+            cmd.arg(&package.name);
+            if let Some(channel) = package.attribute("channel") {
+                cmd.arg(format!("--channel={}", channel));
+            }
+            if package.attributes.contains_key("classic") {
+                cmd.arg("--classic");
+            }
+            cmds.push(cmd);
+        }
+        Ok(cmds)
+    }
+
+    fn install_attributes(&self) -> &'static [&'static str] {
+        &["channel", "classic"]
+    }
+
+    fn remove_cmds(&self, package_list: PackageList, _yes: bool) -> Result<Vec<Command>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.arg("remove");
+        cmd.args(package_list.iter().map(|package| &package.name));
+        Ok(vec![cmd])
+    }
+
+    fn confirms(&self, _operation: Operation) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_snap;
+
+    #[test]
+    fn parses_snaps_excluding_bases_and_snapd() {
+        let rows = [
+            "Name    Version   Rev    Tracking       Publisher   Notes",
+            "core20  20210702  1081   latest/stable  canonical✓  base",
+            "core    16-2.51   11316  latest/stable  canonical✓  core",
+            "snapd   2.51.1    12398  latest/stable  canonical✓  snapd",
+            "code    c3f126316 67     latest/stable  vscode✓     classic",
+            "hello   2.10      38     latest/beta    canonical✓  -",
+        ];
+        let installed: Vec<String> = rows
+            .iter()
+            .filter_map(|line| parse_snap(line))
+            .map(|package| package.to_string())
+            .collect();
+        let expected: Vec<String> = vec![
+            "code channel=latest/stable classic".into(),
+            "hello channel=latest/beta".into(),
+        ];
+        assert_eq!(installed, expected);
+    }
+}
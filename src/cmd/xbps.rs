@@ -0,0 +1,96 @@
+use super::output::Output;
+use super::{package_names, Installed, Package, PackageList, PackageManagerCmds};
+use crate::error::*;
+use std::process::Command;
+
+/// XBPS for Void Linux
+///
+/// Docs: https://docs.voidlinux.org/xbps/index.html
+#[derive(Debug)]
+pub struct XbpsCmd {
+    program: &'static str,
+
+    /// Program used to query the package database
+    query_program: &'static str,
+
+    /// Program used to remove packages
+    remove_program: &'static str,
+}
+
+impl XbpsCmd {
+    pub fn new() -> Self {
+        Self {
+            program: "xbps-install",
+            query_program: "xbps-query",
+            remove_program: "xbps-remove",
+        }
+    }
+}
+
+/// Strip the `-version_revision` suffix from a package
+///
+/// Versions cannot contain a hyphen, so the name is everything before the last one
+fn parse_pkgver(pkgver: &str) -> String {
+    match pkgver.rsplit_once('-') {
+        Some((name, _version)) if !name.is_empty() => name.to_string(),
+        _ => pkgver.to_string(),
+    }
+}
+
+impl PackageManagerCmds for XbpsCmd {
+    fn list_installed(&self) -> Result<Option<Installed>> {
+        let program = self.query_program;
+        let mut cmd = Command::new(program);
+        cmd.arg("-m");
+        let lines = Output::new(&mut cmd, program).read_lines()?;
+        Ok(lines.map(|lines| {
+            lines
+                .iter()
+                .map(|pkgver| Package::new(parse_pkgver(pkgver)))
+                .collect()
+        }))
+    }
+
+    fn install_cmds(&self, package_list: PackageList, yes: bool) -> Result<Vec<Command>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        if yes {
+            cmd.arg("-y");
+        }
+        cmd.args(package_names(&package_list));
+        Ok(vec![cmd])
+    }
+
+    /// Remove packages along with dependencies which aren't needed otherwise
+    fn remove_cmds(&self, package_list: PackageList, yes: bool) -> Result<Vec<Command>> {
+        let program = self.remove_program;
+        let mut cmd = Command::new(program);
+        cmd.arg("-R");
+        if yes {
+            cmd.arg("-y");
+        }
+        cmd.args(package_names(&package_list));
+        Ok(vec![cmd])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_pkgver, XbpsCmd};
+    use crate::cmd::{format_cmd, Package, PackageManagerCmds};
+
+    #[test]
+    fn strips_version_and_revision() {
+        assert_eq!(parse_pkgver("xbps-0.59.1_1"), "xbps");
+        assert_eq!(parse_pkgver("python3-yaml-5.4.1_2"), "python3-yaml");
+        assert_eq!(parse_pkgver("noversion"), "noversion");
+    }
+
+    #[test]
+    fn removes_unneeded_dependencies() {
+        let cmds = XbpsCmd::new()
+            .remove_cmds(vec![Package::new("bat")], true)
+            .unwrap();
+        assert_eq!(format_cmd(&cmds[0]), "xbps-remove -R -y bat");
+    }
+}
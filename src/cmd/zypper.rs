@@ -0,0 +1,96 @@
+use super::output::Output;
+use super::{package_names, Installed, Package, PackageList, PackageManagerCmds};
+use crate::error::*;
+use std::process::Command;
+
+/// Zypper for openSUSE
+///
+/// Docs: https://en.opensuse.org/SDB:Zypper_manual
+#[derive(Debug)]
+pub struct ZypperCmd {
+    program: &'static str,
+}
+
+impl ZypperCmd {
+    pub fn new() -> Self {
+        Self { program: "zypper" }
+    }
+}
+
+/// Parse a row from the `zypper search` table
+///
+/// User requested packages have an `i+` status while automatically
+/// installed dependencies only have `i`
+fn parse_user_installed(line: &str) -> Option<String> {
+    let mut columns = line.split('|').map(str::trim);
+    match (columns.next(), columns.next()) {
+        (Some("i+"), Some(name)) if !name.is_empty() => Some(name.to_string()),
+        _ => None,
+    }
+}
+
+impl PackageManagerCmds for ZypperCmd {
+    fn list_installed(&self) -> Result<Option<Installed>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        cmd.args([
+            "--quiet",
+            "--no-refresh",
+            "search",
+            "--installed-only",
+            "--type",
+            "package",
+        ]);
+        let lines = Output::new(&mut cmd, program).read_lines()?;
+        Ok(lines.map(|lines| {
+            lines
+                .iter()
+                .filter_map(|line| parse_user_installed(line))
+                .map(Package::new)
+                .collect()
+        }))
+    }
+
+    fn install_cmds(&self, package_list: PackageList, yes: bool) -> Result<Vec<Command>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        if yes {
+            cmd.arg("--non-interactive");
+        }
+        cmd.arg("install");
+        cmd.args(package_names(&package_list));
+        Ok(vec![cmd])
+    }
+
+    /// Remove packages along with dependencies which aren't needed otherwise
+    fn remove_cmds(&self, package_list: PackageList, yes: bool) -> Result<Vec<Command>> {
+        let program = self.program;
+        let mut cmd = Command::new(program);
+        if yes {
+            cmd.arg("--non-interactive");
+        }
+        cmd.args(["remove", "--clean-deps"]);
+        cmd.args(package_names(&package_list));
+        Ok(vec![cmd])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_user_installed;
+
+    #[test]
+    fn parses_only_user_installed_rows() {
+        let rows = [
+            "S  | Name      | Summary                | Type",
+            "---+-----------+------------------------+--------",
+            "i+ | neovim    | Vim-fork focused on... | package",
+            "i  | libvterm0 | An implementation of...| package",
+        ];
+        let installed: Vec<String> = rows
+            .iter()
+            .filter_map(|line| parse_user_installed(line))
+            .collect();
+        assert_eq!(installed, vec!["neovim".to_string()]);
+    }
+}
@@ -10,17 +10,31 @@ pub struct Config<'a> {
     pub log_level: LevelFilter,
 
     /// Silence all output
+    #[allow(dead_code)]
     pub quiet: bool,
 
     /// Whether to force writing target file
     pub force: bool,
 
     /// If output should be colorized
+    #[allow(dead_code)]
     pub color: bool,
 
     /// Package manager program
     pub program: Option<&'a str>,
 
+    /// Use every detected package manager
+    pub all: bool,
+
+    /// Whether sync removes installed packages missing from the input
+    pub remove: bool,
+
+    /// Print the packages and commands instead of installing or removing packages
+    pub dry_run: bool,
+
+    /// Install or remove packages without asking for confirmation
+    pub yes: bool,
+
     /// Procedural action to commit
     pub procedure: Procedure,
 
@@ -39,6 +53,10 @@ impl<'a> Default for Config<'a> {
             force: false,
             color: true,
             program: None,
+            all: false,
+            remove: true,
+            dry_run: false,
+            yes: false,
             procedure: Procedure::List,
             output: None,
             input: None,
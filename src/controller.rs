@@ -1,43 +1,406 @@
+use crate::cmd::{format_cmd, PackageList};
 use crate::error::*;
-use crate::io::{Input, Output};
-use crate::pkg::Pkg;
+use crate::io::{confirm, DetailSections, Input, Output, OutputFormat, Sections};
+use crate::manager::ManagerKind;
+use crate::pkg::{Diff, Diffs, Pkg};
+use std::collections::BTreeMap;
+use std::io::{stdin, IsTerminal};
 
 pub struct Controller {
-    pub pkg: Pkg,
+    /// Package managers to operate on, the first being the primary manager
+    pub pkgs: Vec<Pkg>,
+
+    /// Whether every detected package manager is used, with packages sectioned by manager
+    pub all: bool,
 }
 
 /// TODO: print deps count, explicit install count, total, etc
 impl Controller {
-    pub fn new() -> Result<Self> {
-        let pkg = Pkg::init()?;
-        let ctrl = Self { pkg };
+    pub fn new(program: Option<&str>) -> Result<Self> {
+        let pkg = Pkg::init(program)?;
+        let ctrl = Self {
+            pkgs: vec![pkg],
+            all: false,
+        };
         Ok(ctrl)
     }
 
-    pub fn dump<'a>(&self, input: &Option<Input>, output: &Output<'a>, force: bool) -> Result<()> {
-        if let Some(installed) = self.pkg.installed.clone() {
-            let installed = if let Some(input) = input {
+    pub fn all() -> Result<Self> {
+        let pkgs = Pkg::init_all()?;
+        let ctrl = Self { pkgs, all: true };
+        Ok(ctrl)
+    }
+
+    /// Installed packages of a manager which aren't found in the input
+    fn filter_installed(&self, index: usize, input: &Option<Input>) -> Option<PackageList> {
+        let pkg = &self.pkgs[index];
+        pkg.installed.clone().map(|installed| {
+            if let Some(input) = input {
+                let list = input.get_packages(pkg.manager.kind, index == 0);
                 installed
                     .into_iter()
-                    .filter(|item| !input.list.contains(item))
+                    .filter(|item| !list.iter().any(|p| p.name == item.name))
                     .collect()
             } else {
                 installed
-            };
-            let last_index = installed.len() - 1;
-            let installed_new_lines = installed
-                .into_iter()
-                .enumerate()
-                .map(|(i, s)| s + if i != last_index { "\n" } else { "" });
-            let content = installed_new_lines.collect::<String>();
-            output.write(content, force)
+            }
+        })
+    }
+
+    /// Sections of the input for managers which aren't in use
+    fn unused_sections(&self, input: &Input) -> Vec<ManagerKind> {
+        input
+            .sections
+            .keys()
+            .filter(|kind| !self.pkgs.iter().any(|pkg| pkg.manager.kind == **kind))
+            .copied()
+            .collect()
+    }
+
+    fn warn_unused_sections(&self, input: &Input) {
+        for kind in self.unused_sections(input) {
+            let kind = kind.to_string().to_lowercase();
+            if self.all {
+                warn!(
+                    "Skipping the [{}] section since {} wasn't detected",
+                    kind, kind
+                );
+            } else {
+                warn!(
+                    "Skipping the [{}] section since {} isn't the package manager in use, see --all",
+                    kind, kind
+                );
+            }
+        }
+    }
+
+    /// Details of each manager's installed packages, only listed for JSON records
+    fn list_details(&self, output: &Output) -> Result<DetailSections> {
+        let mut details = DetailSections::new();
+        if output.format == OutputFormat::Json {
+            for pkg in &self.pkgs {
+                details.insert(pkg.manager.kind, pkg.manager.cmd.list_details()?);
+            }
+        }
+        Ok(details)
+    }
+
+    pub fn dump<'a>(&self, input: &Option<Input>, output: &Output<'a>, force: bool) -> Result<()> {
+        if let Some(input) = input {
+            self.warn_unused_sections(input);
+        }
+        let details = self.list_details(output)?;
+        if self.all {
+            let mut sections = Sections::new();
+            for (index, pkg) in self.pkgs.iter().enumerate() {
+                match self.filter_installed(index, input) {
+                    Some(installed) => {
+                        sections.insert(pkg.manager.kind, installed);
+                    }
+                    None => info!(
+                        "Packages were not found for {}",
+                        pkg.manager.get_kind_lowercase()
+                    ),
+                }
+            }
+            output.write(&[], &sections, &details, force)
+        } else if let Some(installed) = self.filter_installed(0, input) {
+            if output.format == OutputFormat::Json {
+                // Records always name their manager
+                let mut sections = Sections::new();
+                sections.insert(self.pkgs[0].manager.kind, installed);
+                output.write(&[], &sections, &details, force)
+            } else {
+                output.write(&installed, &Sections::new(), &details, force)
+            }
+        } else {
+            Err(ErrorKind::PackagesNotFound(self.pkgs[0].manager.get_kind_lowercase()).into())
+        }
+    }
+
+    /// Compare the input with each manager's installed packages
+    ///
+    /// Managers which aren't included in the input are never compared, since every
+    /// installed package would be extra. When every manager is used they're skipped,
+    /// otherwise the comparison fails.
+    pub fn diff(&self, input: &Input) -> Result<Diffs> {
+        self.warn_unused_sections(input);
+        let mut diffs = Diffs::new();
+        for (index, pkg) in self.pkgs.iter().enumerate() {
+            let kind = pkg.manager.kind;
+            if !input.includes(kind, index == 0) {
+                if !self.all {
+                    return Err(ErrorKind::FailedManager(format!(
+                        "No packages for {} were found in the input",
+                        pkg.manager.get_kind_lowercase()
+                    ))
+                    .into());
+                }
+                debug!(
+                    "Skipping {} since it isn't included in the input",
+                    pkg.manager.get_kind_lowercase()
+                );
+                continue;
+            }
+            diffs.insert(kind, pkg.diff(input.get_packages(kind, index == 0)));
+        }
+        Ok(diffs)
+    }
+
+    /// Install missing packages and remove extra packages so that each manager matches
+    /// the input, once a summary of the changes is confirmed
+    pub fn sync(&mut self, input: &Input, remove: bool, dry_run: bool, yes: bool) -> Result<()> {
+        let mut plan = self.diff(input)?;
+        plan.retain(|_, diff| {
+            diff.present.clear();
+            if !remove {
+                diff.extra.clear();
+            }
+            diff.has_drift()
+        });
+        if plan.is_empty() {
+            info!("Installed packages already match the input");
+            return Ok(());
+        }
+        if dry_run {
+            return self.write_plan(&plan, yes);
+        }
+        Output::new(None, None).write_diff(&plan, self.all)?;
+        if !yes {
+            self.confirm_sync(&plan)?;
+        }
+        let mut failed: Vec<String> = Vec::new();
+        for pkg in self.pkgs.iter_mut() {
+            if let Some(Diff { missing, extra, .. }) = plan.remove(&pkg.manager.kind) {
+                let mut result = Ok(());
+                if !missing.is_empty() {
+                    result = pkg.install_missing(missing, yes);
+                }
+                if result.is_ok() && !extra.is_empty() {
+                    result = pkg.remove_installed(extra, yes);
+                }
+                if let Err(error) = result {
+                    if !self.all {
+                        return Err(error);
+                    }
+                    error!("{}", error);
+                    failed.push(pkg.manager.get_kind_lowercase());
+                }
+            }
+        }
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(ErrorKind::FailedManager(format!(
+                "Failed to sync packages for: {}",
+                failed.join(" ")
+            ))
+            .into())
+        }
+    }
+
+    /// Ask to continue with the changes of a sync
+    fn confirm_sync(&self, plan: &Diffs) -> Result<()> {
+        if !stdin().is_terminal() {
+            return Err(ErrorKind::ConfirmationRequired("sync packages".into()).into());
+        }
+        let (install_count, remove_count) = plan.values().fold((0, 0), |(i, r), diff| {
+            (i + diff.missing.len(), r + diff.extra.len())
+        });
+        let prompt = format!(
+            "Install {} and remove {} packages?",
+            install_count, remove_count
+        );
+        if confirm(&prompt)? {
+            Ok(())
+        } else {
+            Err(ErrorKind::InterruptedManager("Sync cancelled".into()).into())
+        }
+    }
+
+    /// Print the packages each manager would install or remove, along with the
+    /// commands that would be run
+    fn write_plan(&self, plan: &Diffs, yes: bool) -> Result<()> {
+        if plan.is_empty() {
+            warn!("No packages found to install or remove");
+            return Ok(());
+        }
+        let mut cmd_lines: BTreeMap<ManagerKind, Vec<String>> = BTreeMap::new();
+        for pkg in &self.pkgs {
+            if let Some(diff) = plan.get(&pkg.manager.kind) {
+                let mut cmds = Vec::new();
+                if !diff.missing.is_empty() {
+                    cmds.extend(pkg.install_cmds(diff.missing.clone(), yes)?);
+                }
+                if !diff.extra.is_empty() {
+                    cmds.extend(pkg.manager.cmd.remove_cmds(diff.extra.clone(), yes)?);
+                }
+                cmd_lines.insert(pkg.manager.kind, cmds.iter().map(format_cmd).collect());
+            }
+        }
+        Output::new(None, None).write_plan(plan, &cmd_lines, self.all)
+    }
+
+    /// Install the packages of each manager's section, continuing past failures of a
+    /// single manager when every manager is used
+    pub fn install(&mut self, input: &Input, dry_run: bool, yes: bool) -> Result<()> {
+        self.warn_unused_sections(input);
+        if dry_run {
+            let mut plan = Diffs::new();
+            for (index, pkg) in self.pkgs.iter_mut().enumerate() {
+                let list = input.get_packages(pkg.manager.kind, index == 0);
+                pkg.set_missing(list);
+                let missing = pkg.missing.clone().unwrap_or_default();
+                if !missing.is_empty() {
+                    plan.insert(
+                        pkg.manager.kind,
+                        Diff {
+                            missing,
+                            ..Default::default()
+                        },
+                    );
+                }
+            }
+            return self.write_plan(&plan, yes);
+        }
+        let mut failed: Vec<String> = Vec::new();
+        for (index, pkg) in self.pkgs.iter_mut().enumerate() {
+            let list = input.get_packages(pkg.manager.kind, index == 0);
+            if list.is_empty() && self.all {
+                continue;
+            }
+            if let Err(error) = pkg.install_missing(list, yes) {
+                if !self.all {
+                    return Err(error);
+                }
+                error!("{}", error);
+                failed.push(pkg.manager.get_kind_lowercase());
+            }
+        }
+        if failed.is_empty() {
+            Ok(())
         } else {
-            Err(ErrorKind::PackagesNotFound(self.pkg.manager.get_kind_lowercase()).into())
+            Err(ErrorKind::FailedManager(format!(
+                "Failed to install packages for: {}",
+                failed.join(" ")
+            ))
+            .into())
         }
     }
 
-    pub fn install(&mut self, input: &Input) -> Result<()> {
-        self.pkg.install_missing(input.list.clone())?;
-        Ok(())
+    /// Remove the installed packages of each manager's section, continuing past failures
+    /// of a single manager when every manager is used
+    pub fn remove(&mut self, input: &Input, dry_run: bool, yes: bool) -> Result<()> {
+        self.warn_unused_sections(input);
+        if dry_run {
+            let mut plan = Diffs::new();
+            for (index, pkg) in self.pkgs.iter().enumerate() {
+                let list = input.get_packages(pkg.manager.kind, index == 0);
+                let extra = pkg.find_installed(&list);
+                if !extra.is_empty() {
+                    plan.insert(
+                        pkg.manager.kind,
+                        Diff {
+                            extra,
+                            ..Default::default()
+                        },
+                    );
+                }
+            }
+            return self.write_plan(&plan, yes);
+        }
+        let mut failed: Vec<String> = Vec::new();
+        for (index, pkg) in self.pkgs.iter_mut().enumerate() {
+            let list = input.get_packages(pkg.manager.kind, index == 0);
+            if list.is_empty() && self.all {
+                continue;
+            }
+            if let Err(error) = pkg.remove_installed(list, yes) {
+                if !self.all {
+                    return Err(error);
+                }
+                error!("{}", error);
+                failed.push(pkg.manager.get_kind_lowercase());
+            }
+        }
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(ErrorKind::FailedManager(format!(
+                "Failed to remove packages for: {}",
+                failed.join(" ")
+            ))
+            .into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Controller;
+    use crate::cmd::Package;
+    use crate::io::Input;
+    use crate::manager::{Manager, ManagerKind};
+    use crate::pkg::Pkg;
+
+    fn pkg(kind: ManagerKind, installed: &[&str]) -> Pkg {
+        Pkg {
+            manager: Manager::from_kind(kind),
+            installed: Some(installed.iter().map(|name| Package::new(*name)).collect()),
+            missing: None,
+        }
+    }
+
+    #[test]
+    fn sync_never_removes_packages_of_managers_missing_from_input() {
+        let input = Input::from_file("test/flatpak.txt".into()).unwrap();
+        let mut ctrl = Controller {
+            pkgs: vec![pkg(ManagerKind::Pacman, &["bat", "lsd"])],
+            all: false,
+        };
+        assert!(ctrl.diff(&input).is_err(), "Compared pacman without input");
+        assert!(
+            ctrl.sync(&input, true, true, true).is_err(),
+            "Planned to sync pacman without input"
+        );
+    }
+
+    #[test]
+    fn diff_skips_managers_missing_from_input_for_every_manager() {
+        let input = Input::from_file("test/flatpak.txt".into()).unwrap();
+        let ctrl = Controller {
+            pkgs: vec![
+                pkg(ManagerKind::Pacman, &["bat", "lsd"]),
+                pkg(ManagerKind::Flatpak, &["org.mozilla.firefox"]),
+            ],
+            all: true,
+        };
+        let diffs = ctrl.diff(&input).unwrap();
+        assert!(!diffs.contains_key(&ManagerKind::Pacman));
+        assert!(!diffs[&ManagerKind::Flatpak].has_drift());
+    }
+
+    #[test]
+    fn finds_sections_of_managers_not_in_use() {
+        let input = Input::from_file("test/sections.txt".into()).unwrap();
+        let ctrl = Controller {
+            pkgs: vec![pkg(ManagerKind::Pacman, &["bat"])],
+            all: false,
+        };
+        assert_eq!(ctrl.unused_sections(&input), vec![ManagerKind::Flatpak]);
+    }
+
+    #[test]
+    fn installs_with_a_manager_without_installed_packages() {
+        let input = Input::from_list(vec![Package::new("org.mozilla.firefox")]);
+        let mut ctrl = Controller {
+            pkgs: vec![Pkg {
+                installed: None,
+                ..pkg(ManagerKind::Flatpak, &[])
+            }],
+            all: false,
+        };
+        assert!(ctrl.install(&input, true, true).is_ok());
+        assert_eq!(ctrl.pkgs[0].missing, Some(input.list));
     }
 }
@@ -5,6 +5,9 @@ error_chain! {
     foreign_links {
         Clap(::clap::Error);
         Io(::std::io::Error);
+        Json(::serde_json::Error);
+        Toml(::toml::de::Error);
+        Yaml(::serde_yaml::Error);
         ParseIntError(::std::num::ParseIntError);
         Utf8Error(::std::str::Utf8Error);
     }
@@ -26,6 +29,14 @@ error_chain! {
             description("package manager failure"),
             display("{}", error),
         }
+        MalformedInput(filename: String, error: String) {
+            description("malformed input")
+            display("Malformed input '{}': {}", filename, error)
+        }
+        ConfirmationRequired(target: String) {
+            description("confirmation required without a terminal")
+            display("Confirmation is required to {}, but stdin isn't a terminal; use --yes to continue without confirmation", target)
+        }
         PackagesNotFound(manager: String) {
             description("packages not found")
             display("Packages were not found for {}", manager)
@@ -0,0 +1,206 @@
+use super::{DetailSections, Sections};
+use crate::cmd::{Detail, Package, PackageList};
+use crate::error::*;
+use crate::manager::ManagerKind;
+use crate::pkg::Diffs;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::str::FromStr;
+
+/// Fields of a record reported from a package's details, never read as attributes
+const DETAIL_FIELDS: [&str; 2] = ["version", "reason"];
+
+/// Package record, with attributes and details such as `version` and `reason`
+/// alongside the name
+#[derive(Debug, Serialize, Deserialize)]
+struct Record {
+    name: String,
+
+    /// Unset for packages which don't belong to a specific manager
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    manager: Option<String>,
+
+    #[serde(flatten)]
+    attributes: Map<String, Value>,
+}
+
+impl Record {
+    /// Record with flags set to `true`
+    fn new(package: &Package, manager: Option<ManagerKind>) -> Self {
+        Self::with_detail(package, manager, None)
+    }
+
+    /// Record of an installed package with its version and install reason
+    fn with_detail(
+        package: &Package,
+        manager: Option<ManagerKind>,
+        detail: Option<&Detail>,
+    ) -> Self {
+        let mut attributes: Map<String, Value> = package
+            .attributes
+            .iter()
+            .map(|(key, value)| {
+                let value = if value.is_empty() {
+                    Value::Bool(true)
+                } else {
+                    Value::String(value.clone())
+                };
+                (key.clone(), value)
+            })
+            .collect();
+        if let Some(detail) = detail {
+            let fields = DETAIL_FIELDS.iter().zip([&detail.version, &detail.reason]);
+            for (key, value) in fields {
+                if let Some(value) = value {
+                    attributes.insert(key.to_string(), Value::String(value.clone()));
+                }
+            }
+        }
+        Self {
+            name: package.name.clone(),
+            manager: manager.map(|kind| kind.to_string().to_lowercase()),
+            attributes,
+        }
+    }
+
+    fn into_package(self) -> Result<Package> {
+        let name = self.name;
+        self.attributes
+            .into_iter()
+            .filter(|(key, _)| !DETAIL_FIELDS.contains(&key.as_str()))
+            .try_fold(
+                Package::new(name.as_str()),
+                |package, (key, value)| match value {
+                    Value::String(value) => Ok(package.with_attribute(key, value)),
+                    Value::Bool(true) => Ok(package.with_attribute(key, "")),
+                    Value::Bool(false) | Value::Null => Ok(package),
+                    Value::Number(value) => Ok(package.with_attribute(key, value.to_string())),
+                    _ => Err(format!(
+                        "Unsupported value for attribute '{}' of package '{}'",
+                        key, name
+                    )
+                    .into()),
+                },
+            )
+    }
+}
+
+/// Format an array of package records, unsectioned packages first
+pub fn format(list: &[Package], sections: &Sections, details: &DetailSections) -> Result<String> {
+    let records: Vec<Record> = list
+        .iter()
+        .map(|package| Record::new(package, None))
+        .chain(sections.iter().flat_map(|(kind, list)| {
+            let details = details.get(kind);
+            list.iter().map(move |package| {
+                let detail = details.and_then(|details| details.get(&package.name));
+                Record::with_detail(package, Some(*kind), detail)
+            })
+        }))
+        .collect();
+    Ok(serde_json::to_string_pretty(&records)?)
+}
+
+/// Package records of every manager's comparison
+#[derive(Debug, Default, Serialize)]
+struct DiffRecords {
+    missing: Vec<Record>,
+    extra: Vec<Record>,
+    present: Vec<Record>,
+}
+
+/// Format an object with arrays of missing, extra and present package records
+pub fn format_diff(diffs: &Diffs) -> Result<String> {
+    let mut records = DiffRecords::default();
+    for (kind, diff) in diffs {
+        let kind = Some(*kind);
+        records.missing.extend(
+            diff.missing
+                .iter()
+                .map(|package| Record::new(package, kind)),
+        );
+        records
+            .extra
+            .extend(diff.extra.iter().map(|package| Record::new(package, kind)));
+        records.present.extend(
+            diff.present
+                .iter()
+                .map(|package| Record::new(package, kind)),
+        );
+    }
+    Ok(serde_json::to_string_pretty(&records)?)
+}
+
+/// Parse an array of package records, grouping them by manager
+pub fn parse(content: &str) -> Result<(PackageList, Sections)> {
+    let mut list: PackageList = Vec::new();
+    let mut sections = Sections::new();
+    let records: Vec<Record> = serde_json::from_str(content)?;
+    for record in records {
+        let manager = record.manager.clone();
+        let package = record.into_package()?;
+        match manager {
+            Some(manager) => {
+                let kind = ManagerKind::from_str(&manager)
+                    .map_err(|_| Error::from(format!("Unknown package manager '{}'", manager)))?;
+                sections.entry(kind).or_default().push(package);
+            }
+            None => list.push(package),
+        }
+    }
+    Ok((list, sections))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format, parse};
+    use crate::cmd::{Detail, Details, Package, PackageList};
+    use crate::io::{DetailSections, Sections};
+    use crate::manager::ManagerKind;
+
+    #[test]
+    fn json_round_trip() {
+        let list: PackageList = vec![Package::new("bat")];
+        let mut sections = Sections::new();
+        sections.insert(ManagerKind::Pacman, vec![Package::new("lsd")]);
+        sections.insert(
+            ManagerKind::Snap,
+            vec![Package::new("code").with_attribute("classic", "")],
+        );
+        let detail = Detail {
+            version: Some("0.20.1".into()),
+            reason: Some("explicit".into()),
+        };
+        let mut details = DetailSections::new();
+        details.insert(
+            ManagerKind::Pacman,
+            Details::from([("lsd".to_string(), detail)]),
+        );
+        let content = format(&list, &sections, &details).unwrap();
+        let records: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(
+            records,
+            serde_json::json!([
+                { "name": "bat" },
+                { "name": "lsd", "manager": "pacman", "version": "0.20.1", "reason": "explicit" },
+                { "name": "code", "manager": "snap", "classic": true },
+            ])
+        );
+        let parsed = parse(&content);
+        assert!(parsed.is_ok(), "Failed to parse formatted json");
+        assert_eq!(parsed.unwrap(), (list, sections));
+    }
+
+    #[test]
+    fn json_rejects_unknown_managers() {
+        let parsed = parse(r#"[{ "name": "bat", "manager": "unknown" }]"#);
+        assert!(parsed.is_err());
+    }
+
+    #[test]
+    fn json_never_reads_details_as_attributes() {
+        let (_, sections) =
+            parse(r#"[{ "name": "ripgrep", "manager": "cargo", "version": "13.0.0" }]"#).unwrap();
+        assert_eq!(sections[&ManagerKind::Cargo], vec![Package::new("ripgrep")]);
+    }
+}
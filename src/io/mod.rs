@@ -0,0 +1,643 @@
+use crate::cmd::{Details, Package, PackageList};
+use crate::error::*;
+use crate::manager::ManagerKind;
+use crate::pkg::{Diff, Diffs};
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::fs::{self, OpenOptions};
+use std::io::{stdin, stdout, Write};
+use std::io::{BufRead, BufWriter};
+use std::path::Path;
+use std::str::FromStr;
+
+mod json;
+mod toml;
+mod yaml;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    /// Print output to console
+    Stdout,
+
+    // Format to txt file
+    Txt,
+
+    // Format to toml file
+    Toml,
+
+    // Format to yaml file
+    Yaml,
+
+    // Format to json file
+    Json,
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = Error;
+
+    /// Parse a format name, which is also its file extension
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "txt" => Ok(OutputFormat::Txt),
+            "toml" => Ok(OutputFormat::Toml),
+            "yml" | "yaml" => Ok(OutputFormat::Yaml),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(format!("Unsupported output format '{}'", s).into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Output<'a> {
+    /// Output format type
+    pub format: OutputFormat,
+
+    /// Target file
+    pub filename: Option<&'a str>,
+}
+
+impl<'a> Output<'a> {
+    /// An explicit format takes precedence over the filename's extension
+    pub fn new(filename: Option<&'a str>, format: Option<OutputFormat>) -> Self {
+        let format = match (format, filename) {
+            (Some(format), _) => format,
+            (None, Some(filename)) => parse_output_format(filename),
+            (None, None) => OutputFormat::Stdout,
+        };
+        Self { format, filename }
+    }
+
+    /// Write unsectioned packages followed by each manager's section
+    ///
+    /// Details are only written to JSON records, since manifests are read back
+    pub fn write(
+        &self,
+        list: &[Package],
+        sections: &Sections,
+        details: &DetailSections,
+        force: bool,
+    ) -> Result<()> {
+        let content = match self.format {
+            OutputFormat::Stdout | OutputFormat::Txt => format_txt(list, sections),
+            OutputFormat::Toml => toml::format(list, sections),
+            OutputFormat::Yaml => yaml::format(list, sections)?,
+            OutputFormat::Json => json::format(list, sections, details)?,
+        };
+        self.write_content(content, force)
+    }
+
+    /// Write each manager's comparison, with headers when there are several managers
+    pub fn write_diff(&self, diffs: &Diffs, sectioned: bool) -> Result<()> {
+        let content = match self.format {
+            OutputFormat::Json => json::format_diff(diffs)?,
+            _ => format_diff(diffs, sectioned),
+        };
+        self.write_content(content, false)
+    }
+
+    /// Write the packages each manager would change followed by its commands
+    pub fn write_plan(
+        &self,
+        plan: &Diffs,
+        cmd_lines: &BTreeMap<ManagerKind, Vec<String>>,
+        sectioned: bool,
+    ) -> Result<()> {
+        self.write_content(format_plan(plan, cmd_lines, sectioned), false)
+    }
+
+    fn write_content(&self, content: String, force: bool) -> Result<()> {
+        match (self.format, self.filename) {
+            (OutputFormat::Stdout, _) | (_, None) => {
+                writeln!(stdout(), "{}", content)?;
+                Ok(())
+            }
+            (_, Some(filename)) => {
+                let file = OpenOptions::new()
+                    .create_new(!force)
+                    .read(force)
+                    .write(true)
+                    .create(force)
+                    .open(Path::new(filename))?;
+                let mut writer = BufWriter::new(file);
+                writer.write_all(content.as_bytes())?;
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputFormat {
+    /// Print output to console
+    Stdin,
+
+    // Format to txt file
+    Txt,
+
+    // Format to toml file
+    Toml,
+
+    // Format to yaml file
+    Yaml,
+
+    // Format to json file
+    Json,
+}
+
+impl Display for InputFormat {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Ask a yes or no question, defaulting to no
+pub fn confirm(prompt: &str) -> Result<bool> {
+    print!("{} [y/N] ", prompt);
+    stdout().flush()?;
+    let mut answer = String::new();
+    stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Sort packages and remove duplicate names, keeping the first occurrence
+fn sort_and_dedup(list: &mut PackageList) {
+    list.sort_by(|a, b| a.name.cmp(&b.name));
+    list.dedup_by(|a, b| a.name == b.name);
+}
+
+/// Packages grouped under `[manager]` section headers
+pub type Sections = BTreeMap<ManagerKind, PackageList>;
+
+/// Details of each manager's installed packages
+pub type DetailSections = BTreeMap<ManagerKind, Details>;
+
+/// Key for packages which don't belong to a specific manager in structured formats
+const UNSECTIONED: &str = "packages";
+
+/// Parse a `[manager]` section header
+fn parse_section_header(line: &str) -> Option<&str> {
+    line.strip_prefix('[')
+        .and_then(|line| line.strip_suffix(']'))
+        .map(str::trim)
+}
+
+/// Newline separated package lines
+fn format_list(list: &[Package]) -> String {
+    list.iter()
+        .map(|p| p.to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Format packages in the same form they're read from a txt file
+fn format_txt(list: &[Package], sections: &Sections) -> String {
+    let mut blocks: Vec<String> = Vec::new();
+    if !list.is_empty() {
+        blocks.push(format_list(list));
+    }
+    for (kind, list) in sections {
+        let header = format!("[{}]", kind.to_string().to_lowercase());
+        if list.is_empty() {
+            blocks.push(header);
+        } else {
+            blocks.push(format!("{}\n{}", header, format_list(list)));
+        }
+    }
+    blocks.join("\n\n")
+}
+
+/// Lines of a comparison ordered by name, with `+` for missing packages, `-` for extra
+/// packages and a space for present packages
+fn format_diff_lines(diff: &Diff) -> String {
+    let mut lines: Vec<(&str, String)> = diff
+        .missing
+        .iter()
+        .map(|p| (p.name.as_str(), format!("+ {}", p)))
+        .chain(
+            diff.extra
+                .iter()
+                .map(|p| (p.name.as_str(), format!("- {}", p))),
+        )
+        .chain(
+            diff.present
+                .iter()
+                .map(|p| (p.name.as_str(), format!("  {}", p))),
+        )
+        .collect();
+    lines.sort_by(|a, b| a.0.cmp(b.0));
+    lines
+        .into_iter()
+        .map(|(_, line)| line)
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Format each manager's lines in the same form as a txt file's sections
+fn format_blocks<I: IntoIterator<Item = (ManagerKind, String)>>(
+    blocks: I,
+    sectioned: bool,
+) -> String {
+    blocks
+        .into_iter()
+        .map(|(kind, lines)| {
+            if !sectioned {
+                lines
+            } else if lines.is_empty() {
+                format!("[{}]", kind.to_string().to_lowercase())
+            } else {
+                format!("[{}]\n{}", kind.to_string().to_lowercase(), lines)
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+fn format_diff(diffs: &Diffs, sectioned: bool) -> String {
+    let blocks = diffs
+        .iter()
+        .map(|(kind, diff)| (*kind, format_diff_lines(diff)));
+    format_blocks(blocks, sectioned)
+}
+
+/// Comparison lines followed by commands prefixed with `$`
+fn format_plan(
+    plan: &Diffs,
+    cmd_lines: &BTreeMap<ManagerKind, Vec<String>>,
+    sectioned: bool,
+) -> String {
+    let blocks = plan.iter().map(|(kind, diff)| {
+        let mut lines = vec![format_diff_lines(diff)];
+        if let Some(cmd_lines) = cmd_lines.get(kind) {
+            lines.extend(cmd_lines.iter().map(|line| format!("$ {}", line)));
+        }
+        (*kind, lines.join("\n"))
+    });
+    format_blocks(blocks, sectioned)
+}
+
+/// Parse packages preceding any section header and packages within each section
+fn parse_txt<R: BufRead>(reader: R) -> Result<(PackageList, Sections)> {
+    let mut packages: PackageList = Vec::new();
+    let mut sections = Sections::new();
+    let mut section: Option<ManagerKind> = None;
+    for (index, line) in reader.lines().enumerate() {
+        let mut line = line.unwrap_or_default();
+        let first_char = line.chars().next();
+        if first_char != "#".chars().next() && !line.is_empty() {
+            let inner_comment_index = line.chars().position(|c| c == '#');
+            if let Some(inner_comment_index) = inner_comment_index {
+                line = line.chars().take(inner_comment_index).collect();
+            }
+            let line = line.trim();
+            if let Some(header) = parse_section_header(line) {
+                let kind = ManagerKind::from_str(header).map_err(|_| {
+                    Error::from(format!(
+                        "Unknown package manager section '[{}]' at line {}",
+                        header,
+                        index + 1
+                    ))
+                })?;
+                section = Some(kind);
+            } else if !line.is_empty() {
+                let package = line.parse()?;
+                match section {
+                    Some(kind) => sections.entry(kind).or_default().push(package),
+                    None => packages.push(package),
+                }
+            }
+        }
+    }
+    Ok((packages, sections))
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    /// Input format type
+    #[allow(dead_code)]
+    pub format: InputFormat,
+
+    /// Package list to read from
+    pub list: PackageList,
+
+    /// Package lists for specific managers
+    pub sections: Sections,
+}
+
+/// TODO: implement input
+#[allow(dead_code)]
+impl Input {
+    pub fn from_file(filename: String) -> Result<Self> {
+        let format = parse_input_format(&filename);
+        let content = fs::read_to_string(&filename)
+            .map_err(|err| Error::from(format!("Unable to read file '{}': {}", filename, err)))?;
+        let parsed = match format {
+            InputFormat::Toml => toml::parse(&content),
+            InputFormat::Yaml => yaml::parse(&content),
+            InputFormat::Json => json::parse(&content),
+            InputFormat::Stdin | InputFormat::Txt => parse_txt(content.as_bytes()),
+        };
+        let (mut list, mut sections) =
+            parsed.map_err(|err| ErrorKind::MalformedInput(filename.clone(), err.to_string()))?;
+        sort_and_dedup(&mut list);
+        sections.values_mut().for_each(sort_and_dedup);
+        let input = Self {
+            format,
+            list,
+            sections,
+        };
+        Ok(input)
+    }
+
+    pub fn from_list(mut list: PackageList) -> Self {
+        sort_and_dedup(&mut list);
+        Self {
+            format: InputFormat::Stdin,
+            list,
+            sections: Sections::new(),
+        }
+    }
+
+    pub fn append_list(&mut self, list: PackageList) {
+        let mut list = [&self.list[..], &list[..]].concat();
+        sort_and_dedup(&mut list);
+        self.set_list(list);
+    }
+
+    fn set_list(&mut self, list: PackageList) -> &mut Self {
+        self.list = list;
+        self
+    }
+
+    pub fn append_sections(&mut self, sections: Sections) {
+        for (kind, list) in sections {
+            let section = self.sections.entry(kind).or_default();
+            section.extend(list);
+            sort_and_dedup(section);
+        }
+    }
+
+    /// Whether packages are listed for a manager, either in its section or unsectioned
+    /// when it's the primary manager
+    pub fn includes(&self, kind: ManagerKind, primary: bool) -> bool {
+        self.sections.contains_key(&kind) || (primary && !self.list.is_empty())
+    }
+
+    /// Packages in a manager's section, along with the unsectioned packages
+    /// when it's the primary manager
+    pub fn get_packages(&self, kind: ManagerKind, primary: bool) -> PackageList {
+        let mut list = self.sections.get(&kind).cloned().unwrap_or_default();
+        if primary {
+            list.extend(self.list.iter().cloned());
+            sort_and_dedup(&mut list);
+        }
+        list
+    }
+}
+
+fn get_extension_from_filename(filename: &str) -> Option<&str> {
+    Path::new(filename).extension().and_then(OsStr::to_str)
+}
+
+fn parse_output_format(filename: &str) -> OutputFormat {
+    match get_extension_from_filename(filename) {
+        Some(ext) => match OutputFormat::from_str(ext) {
+            Ok(format) => {
+                debug!("Using {} extension found in output filename", ext);
+                format
+            }
+            Err(_) => {
+                warn!(
+                    "Unsupported output format '{}' for output argument '{}', defaulting to plain text",
+                    ext, filename
+                );
+                OutputFormat::Txt
+            }
+        },
+        None => {
+            info!(
+                "No extension found for output argument '{}', defaulting to plain text",
+                filename
+            );
+            OutputFormat::Txt
+        }
+    }
+}
+
+/// TODO: implement input
+#[allow(dead_code)]
+fn parse_input_format(filename: &str) -> InputFormat {
+    match get_extension_from_filename(filename) {
+        Some("txt") => {
+            debug!("Using txt extension found in input filename");
+            InputFormat::Txt
+        }
+        Some("toml") => {
+            debug!("Using toml extension found in input filename");
+            InputFormat::Toml
+        }
+        Some("yml") | Some("yaml") => {
+            debug!("Using yaml extension found in input filename");
+            InputFormat::Yaml
+        }
+        Some("json") => {
+            debug!("Using json extension found in input filename");
+            InputFormat::Json
+        }
+        Some(ext) => {
+            warn!(
+                "Unsupported output format '{}' for <FILE> argument '{}', defaulting to plain text",
+                ext, filename
+            );
+            InputFormat::Txt
+        }
+        None => {
+            info!(
+                "No extension found for output argument '{}', defaulting to plain text",
+                filename
+            );
+            InputFormat::Txt
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_diff, format_plan, format_txt, parse_txt, Input, InputFormat, Sections};
+    use crate::cmd::{Package, PackageList};
+    use crate::manager::ManagerKind;
+    use crate::pkg::{Diff, Diffs};
+    use std::collections::BTreeMap;
+    use std::path::Path;
+
+    #[test]
+    fn input_list_sorts_and_removes_duplicates() {
+        let list: PackageList = vec![Package::new("a"), Package::new("c"), Package::new("b")];
+        let mut duped_list = list.clone();
+        duped_list.push(Package::new("a"));
+        let input = Input::from_list(duped_list);
+        let expected_list: PackageList =
+            vec![Package::new("a"), Package::new("b"), Package::new("c")];
+        assert_eq!(input.list, expected_list);
+        assert_eq!(input.format, InputFormat::Stdin);
+    }
+
+    #[test]
+    fn input_reads_file_sorts_and_removes_duplicates() {
+        let test_file = "test/example.txt";
+        let path = Path::new(test_file);
+        assert!(path.exists(), "Missing test input file");
+        let input = Input::from_file(test_file.into());
+        assert!(input.is_ok(), "Failed to create input");
+        let input = input.unwrap();
+        assert_eq!(input.format, InputFormat::Txt);
+        let expected_list: PackageList = vec![
+            Package::new("bat"),
+            Package::new("broot"),
+            Package::new("lsd"),
+        ];
+        assert_eq!(input.list, expected_list);
+    }
+
+    #[test]
+    fn input_reads_file_preserving_atom_slots() {
+        let input = Input::from_file("test/portage.txt".into());
+        assert!(input.is_ok(), "Failed to create input");
+        let expected_list: PackageList = vec![
+            Package::new("app-editors/neovim"),
+            Package::new("app-shells/zsh"),
+            Package::new("dev-lang/python:3.11"),
+            Package::new("dev-lang/rust:stable"),
+        ];
+        assert_eq!(input.unwrap().list, expected_list);
+    }
+
+    #[test]
+    fn input_reads_file_preserving_scoped_names() {
+        let input = Input::from_file("test/npm.txt".into());
+        assert!(input.is_ok(), "Failed to create input");
+        let expected_list: PackageList = vec![
+            Package::new("@angular/cli"),
+            Package::new("@scope/pkg@1.2.3"),
+            Package::new("typescript"),
+        ];
+        assert_eq!(input.unwrap().list, expected_list);
+    }
+
+    #[test]
+    fn input_reads_file_preserving_outputs() {
+        let input = Input::from_file("test/guix.txt".into());
+        assert!(input.is_ok(), "Failed to create input");
+        let expected_list: PackageList = vec![
+            Package::new("gcc-toolchain:debug"),
+            Package::new("glib:bin"),
+            Package::new("hello"),
+        ];
+        assert_eq!(input.unwrap().list, expected_list);
+    }
+
+    #[test]
+    fn input_reads_file_sections() {
+        let input = Input::from_file("test/sections.txt".into());
+        assert!(input.is_ok(), "Failed to create input");
+        let input = input.unwrap();
+        assert_eq!(input.list, vec![Package::new("bat")]);
+        let expected_list: PackageList = vec![
+            Package::new("bat"),
+            Package::new("broot"),
+            Package::new("lsd"),
+        ];
+        assert_eq!(input.get_packages(ManagerKind::Pacman, true), expected_list);
+        let flatpak = input.get_packages(ManagerKind::Flatpak, false);
+        assert_eq!(flatpak.len(), 1);
+        assert_eq!(flatpak[0].attribute("remote"), Some("flathub"));
+    }
+
+    #[test]
+    fn txt_sections_round_trip() {
+        let list: PackageList = vec![Package::new("bat")];
+        let mut sections = Sections::new();
+        sections.insert(ManagerKind::Pacman, vec![Package::new("lsd")]);
+        sections.insert(
+            ManagerKind::Flatpak,
+            vec![Package::new("org.mozilla.firefox").with_attribute("remote", "flathub")],
+        );
+        let content = format_txt(&list, &sections);
+        assert_eq!(
+            content,
+            "bat\n\n[pacman]\nlsd\n\n[flatpak]\norg.mozilla.firefox remote=flathub"
+        );
+        let parsed = parse_txt(content.as_bytes());
+        assert!(parsed.is_ok(), "Failed to parse formatted sections");
+        assert_eq!(parsed.unwrap(), (list, sections));
+    }
+
+    #[test]
+    fn txt_rejects_unknown_sections() {
+        let parsed = parse_txt("[unknown]\nbat".as_bytes());
+        assert!(parsed.is_err(), "Unknown section was accepted");
+    }
+
+    #[test]
+    fn diff_lines_are_ordered_by_name() {
+        let diff = Diff {
+            missing: vec![Package::new("ripgrep")],
+            extra: vec![Package::new("lsd"), Package::new("vim")],
+            present: vec![Package::new("bat")],
+        };
+        assert!(diff.has_drift());
+        let mut diffs = Diffs::new();
+        diffs.insert(ManagerKind::Pacman, diff);
+        assert_eq!(format_diff(&diffs, false), "  bat\n- lsd\n+ ripgrep\n- vim");
+        diffs.insert(ManagerKind::Flatpak, Diff::default());
+        assert_eq!(
+            format_diff(&diffs, true),
+            "[pacman]\n  bat\n- lsd\n+ ripgrep\n- vim\n\n[flatpak]"
+        );
+    }
+
+    #[test]
+    fn plan_lists_commands_after_packages() {
+        let mut plan = Diffs::new();
+        plan.insert(
+            ManagerKind::Pacman,
+            Diff {
+                missing: vec![Package::new("broot")],
+                extra: vec![Package::new("lsd")],
+                ..Default::default()
+            },
+        );
+        let mut cmd_lines = BTreeMap::new();
+        cmd_lines.insert(
+            ManagerKind::Pacman,
+            vec![
+                "pacman -Syu broot".to_string(),
+                "pacman -Rns lsd".to_string(),
+            ],
+        );
+        assert_eq!(
+            format_plan(&plan, &cmd_lines, true),
+            "[pacman]\n+ broot\n- lsd\n$ pacman -Syu broot\n$ pacman -Rns lsd"
+        );
+    }
+
+    #[test]
+    fn input_appends_list() {
+        let list: PackageList = vec![Package::new("a"), Package::new("c"), Package::new("b")];
+        let mut input = Input::from_list(list);
+        input.append_list(vec![Package::new("d")]);
+        let expected_list: PackageList = vec![
+            Package::new("a"),
+            Package::new("b"),
+            Package::new("c"),
+            Package::new("d"),
+        ];
+        assert_eq!(input.list, expected_list);
+    }
+}
@@ -0,0 +1,162 @@
+use super::{Sections, UNSECTIONED};
+use crate::cmd::{Package, PackageList};
+use crate::error::*;
+use crate::manager::ManagerKind;
+use std::str::FromStr;
+use toml::Value;
+
+/// Keys which don't need quotes
+fn is_bare_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn format_key(key: &str) -> String {
+    if is_bare_key(key) {
+        key.to_string()
+    } else {
+        Value::String(key.to_string()).to_string()
+    }
+}
+
+/// Package as an inline table of its attributes, with flags set to `true`
+fn format_package(package: &Package) -> String {
+    let attributes: Vec<String> = package
+        .attributes
+        .iter()
+        .map(|(key, value)| {
+            if value.is_empty() {
+                format!("{} = true", format_key(key))
+            } else {
+                format!("{} = {}", format_key(key), Value::String(value.clone()))
+            }
+        })
+        .collect();
+    if attributes.is_empty() {
+        format!("{} = {{}}", format_key(&package.name))
+    } else {
+        format!(
+            "{} = {{ {} }}",
+            format_key(&package.name),
+            attributes.join(", ")
+        )
+    }
+}
+
+fn format_table(name: &str, list: &[Package]) -> String {
+    let mut table = format!("[{}]\n", name);
+    for package in list {
+        table.push_str(&format_package(package));
+        table.push('\n');
+    }
+    table
+}
+
+/// Format a table for unsectioned packages and a table for each manager, with a key
+/// for each package
+pub fn format(list: &[Package], sections: &Sections) -> String {
+    let mut tables: Vec<String> = Vec::new();
+    if !list.is_empty() {
+        tables.push(format_table(UNSECTIONED, list));
+    }
+    for (kind, list) in sections {
+        tables.push(format_table(&kind.to_string().to_lowercase(), list));
+    }
+    tables.join("\n")
+}
+
+/// Parse a package from its key and either an attribute table or a version string
+fn parse_package(name: &str, value: &Value) -> Result<Package> {
+    let package = Package::new(name);
+    match value {
+        Value::String(version) => Ok(package.with_attribute("version", version.as_str())),
+        Value::Table(attributes) => attributes
+            .iter()
+            .try_fold(package, |package, (key, value)| match value {
+                Value::String(value) => Ok(package.with_attribute(key.as_str(), value.as_str())),
+                Value::Boolean(true) => Ok(package.with_attribute(key.as_str(), "")),
+                Value::Boolean(false) => Ok(package),
+                Value::Integer(_) | Value::Float(_) => {
+                    Ok(package.with_attribute(key.as_str(), value.to_string()))
+                }
+                _ => Err(Error::from(format!(
+                    "Unsupported value for attribute '{}' of package '{}'",
+                    key, name
+                ))),
+            }),
+        _ => Err(format!("Expected a table or version for package '{}'", name).into()),
+    }
+}
+
+/// Parse unsectioned packages and packages in each manager's table
+pub fn parse(content: &str) -> Result<(PackageList, Sections)> {
+    let tables: toml::value::Table = toml::from_str(content)?;
+    let mut list: PackageList = Vec::new();
+    let mut sections = Sections::new();
+    for (name, table) in &tables {
+        let packages = match table {
+            Value::Table(packages) => packages
+                .iter()
+                .map(|(package, value)| parse_package(package, value))
+                .collect::<Result<PackageList>>()?,
+            _ => return Err(format!("Expected a table of packages for '{}'", name).into()),
+        };
+        if name == UNSECTIONED {
+            list.extend(packages);
+        } else {
+            let kind = ManagerKind::from_str(name)
+                .map_err(|_| Error::from(format!("Unknown package manager table '[{}]'", name)))?;
+            sections.entry(kind).or_default().extend(packages);
+        }
+    }
+    Ok((list, sections))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format, parse};
+    use crate::cmd::{Package, PackageList};
+    use crate::io::Sections;
+    use crate::manager::ManagerKind;
+
+    #[test]
+    fn toml_round_trip() {
+        let list: PackageList = vec![Package::new("bat")];
+        let mut sections = Sections::new();
+        sections.insert(
+            ManagerKind::Pacman,
+            vec![Package::new("lsd")
+                .with_attribute("note", "ls replacement")
+                .with_attribute("repository", "community")
+                .with_attribute("version", ">=0.20")],
+        );
+        sections.insert(
+            ManagerKind::Snap,
+            vec![Package::new("code").with_attribute("classic", "")],
+        );
+        let content = format(&list, &sections);
+        assert_eq!(
+            content,
+            "[packages]\nbat = {}\n\n[pacman]\nlsd = { note = \"ls replacement\", repository = \"community\", version = \">=0.20\" }\n\n[snap]\ncode = { classic = true }\n"
+        );
+        let parsed = parse(&content);
+        assert!(parsed.is_ok(), "Failed to parse formatted toml");
+        assert_eq!(parsed.unwrap(), (list, sections));
+    }
+
+    #[test]
+    fn toml_parses_version_shorthand_and_quoted_names() {
+        let content = "[npm]\n\"@angular/cli\" = \"^13\"\n";
+        let (_, sections) = parse(content).unwrap();
+        let npm = &sections[&ManagerKind::Npm];
+        assert_eq!(npm[0].name, "@angular/cli");
+        assert_eq!(npm[0].attribute("version"), Some("^13"));
+    }
+
+    #[test]
+    fn toml_rejects_unknown_tables() {
+        assert!(parse("[unknown]\nbat = {}\n").is_err());
+    }
+}
@@ -0,0 +1,178 @@
+use super::{Sections, UNSECTIONED};
+use crate::cmd::{Package, PackageList};
+use crate::error::*;
+use crate::manager::ManagerKind;
+use serde_yaml::{Mapping, Value};
+use std::str::FromStr;
+
+/// Package attributes as a mapping, with flags set to `true`
+fn format_packages(list: &[Package]) -> Value {
+    let packages: Mapping = list
+        .iter()
+        .map(|package| {
+            let attributes: Mapping = package
+                .attributes
+                .iter()
+                .map(|(key, value)| {
+                    let value = if value.is_empty() {
+                        Value::Bool(true)
+                    } else {
+                        Value::String(value.clone())
+                    };
+                    (Value::String(key.clone()), value)
+                })
+                .collect();
+            (
+                Value::String(package.name.clone()),
+                Value::Mapping(attributes),
+            )
+        })
+        .collect();
+    Value::Mapping(packages)
+}
+
+/// Format a mapping of unsectioned packages and a mapping for each manager, with a key
+/// for each package
+pub fn format(list: &[Package], sections: &Sections) -> Result<String> {
+    let mut manifest = Mapping::new();
+    if !list.is_empty() {
+        manifest.insert(UNSECTIONED.into(), format_packages(list));
+    }
+    for (kind, list) in sections {
+        manifest.insert(
+            kind.to_string().to_lowercase().into(),
+            format_packages(list),
+        );
+    }
+    Ok(serde_yaml::to_string(&manifest)?)
+}
+
+/// Scalar attribute values are kept as strings
+fn parse_attribute(package: Package, key: &str, value: &Value) -> Result<Package> {
+    match value {
+        Value::String(value) => Ok(package.with_attribute(key, value.as_str())),
+        Value::Bool(true) => Ok(package.with_attribute(key, "")),
+        Value::Bool(false) | Value::Null => Ok(package),
+        Value::Number(value) => Ok(package.with_attribute(key, value.to_string())),
+        _ => Err(format!(
+            "Unsupported value for attribute '{}' of package '{}'",
+            key, package.name
+        )
+        .into()),
+    }
+}
+
+/// Parse a package from its key and either attributes, a version string or nothing
+fn parse_package(name: &Value, value: &Value) -> Result<Package> {
+    let name = name
+        .as_str()
+        .ok_or_else(|| Error::from(format!("Expected a package name, found {:?}", name)))?;
+    let package = Package::new(name);
+    match value {
+        Value::Null => Ok(package),
+        Value::String(version) => Ok(package.with_attribute("version", version.as_str())),
+        Value::Mapping(attributes) => {
+            attributes
+                .iter()
+                .try_fold(package, |package, (key, value)| match key.as_str() {
+                    Some(key) => parse_attribute(package, key, value),
+                    None => {
+                        Err(format!("Expected an attribute name for package '{}'", name).into())
+                    }
+                })
+        }
+        _ => Err(format!("Expected attributes or a version for package '{}'", name).into()),
+    }
+}
+
+/// Packages are a mapping of names to attributes, or a sequence of names
+fn parse_packages(manager: &str, packages: &Value) -> Result<PackageList> {
+    match packages {
+        Value::Null => Ok(Vec::new()),
+        Value::Mapping(packages) => packages
+            .iter()
+            .map(|(name, value)| parse_package(name, value))
+            .collect(),
+        Value::Sequence(names) => names
+            .iter()
+            .map(|name| parse_package(name, &Value::Null))
+            .collect(),
+        _ => Err(format!("Expected packages for '{}'", manager).into()),
+    }
+}
+
+/// Parse unsectioned packages and packages for each manager
+pub fn parse(content: &str) -> Result<(PackageList, Sections)> {
+    let mut list: PackageList = Vec::new();
+    let mut sections = Sections::new();
+    if content.trim().is_empty() {
+        return Ok((list, sections));
+    }
+    let manifest: Mapping = serde_yaml::from_str(content)?;
+    for (manager, packages) in &manifest {
+        let manager = manager.as_str().ok_or_else(|| {
+            Error::from(format!("Expected a package manager, found {:?}", manager))
+        })?;
+        let packages = parse_packages(manager, packages)?;
+        if manager == UNSECTIONED {
+            list.extend(packages);
+        } else {
+            let kind = ManagerKind::from_str(manager)
+                .map_err(|_| Error::from(format!("Unknown package manager '{}'", manager)))?;
+            sections.entry(kind).or_default().extend(packages);
+        }
+    }
+    Ok((list, sections))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format, parse};
+    use crate::cmd::{Package, PackageList};
+    use crate::io::Sections;
+    use crate::manager::ManagerKind;
+
+    #[test]
+    fn yaml_round_trip() {
+        let list: PackageList = vec![Package::new("bat")];
+        let mut sections = Sections::new();
+        sections.insert(
+            ManagerKind::Pacman,
+            vec![Package::new("lsd").with_attribute("version", ">=0.20")],
+        );
+        sections.insert(
+            ManagerKind::Snap,
+            vec![Package::new("code").with_attribute("classic", "")],
+        );
+        let content = format(&list, &sections).unwrap();
+        assert_eq!(
+            content,
+            "packages:\n  bat: {}\npacman:\n  lsd:\n    version: '>=0.20'\nsnap:\n  code:\n    classic: true\n"
+        );
+        let parsed = parse(&content);
+        assert!(parsed.is_ok(), "Failed to parse formatted yaml");
+        assert_eq!(parsed.unwrap(), (list, sections));
+    }
+
+    #[test]
+    fn yaml_parses_sequences_and_empty_packages() {
+        let content = "pacman: [bat, lsd]\nnpm:\n  \"@angular/cli\":\n";
+        let (_, sections) = parse(content).unwrap();
+        assert_eq!(
+            sections[&ManagerKind::Pacman],
+            vec![Package::new("bat"), Package::new("lsd")]
+        );
+        assert_eq!(
+            sections[&ManagerKind::Npm],
+            vec![Package::new("@angular/cli")]
+        );
+    }
+
+    #[test]
+    fn yaml_reports_location_of_malformed_input() {
+        let parsed = parse("pacman:\n  bat: {}\n lsd: {}\n");
+        assert!(parsed.is_err());
+        let error = parsed.unwrap_err().to_string();
+        assert!(error.contains("line 3"), "Missing location in '{}'", error);
+    }
+}
@@ -3,7 +3,7 @@ extern crate log;
 
 use crate::app::{App, Procedure};
 use crate::cli::Cli;
-use crate::error::{default_error_handler, Result};
+use crate::error::{default_error_handler, Error, Result};
 use config::Config;
 use io::{Input, Output};
 use std::process;
@@ -23,34 +23,53 @@ fn run(config: Config) -> Result<bool> {
     App::new(config)?.init()
 }
 
+/// Exit code for errors, which `diff` keeps apart from drift like diff(1) does
+fn error_code(procedure: &Procedure) -> i32 {
+    match procedure {
+        Procedure::Diff => 2,
+        _ => 1,
+    }
+}
+
+fn exit_with_error(error: &Error, quiet: bool, code: i32) -> ! {
+    if !quiet {
+        let stderr = std::io::stderr();
+        default_error_handler(error, &mut stderr.lock());
+    }
+    process::exit(code)
+}
+
 fn main() {
     let cli = Cli::new();
     let quiet = cli.get_quiet();
     let procedure = cli.get_procedure();
-    let (input, output): (Option<Input>, Option<Output>) = match procedure {
+    let code = error_code(&procedure);
+    let (input, output): (Result<Option<Input>>, Option<Output>) = match procedure {
         Procedure::List => (cli.get_list_input(), cli.get_output()),
         Procedure::Install => (cli.get_install_input(), None),
-        Procedure::Test => (None, None),
+        Procedure::Diff => (cli.get_diff_input(), cli.get_output()),
+        Procedure::Sync => (cli.get_sync_input(), None),
+        Procedure::Remove => (cli.get_remove_input(), None),
+        Procedure::Test => (Ok(None), None),
     };
+    let input = input.unwrap_or_else(|error| exit_with_error(&error, quiet, code));
     let config = Config {
         log_level: cli.get_log_level(),
         quiet,
         force: cli.get_force(),
         color: cli.color,
         program: cli.get_program(),
+        all: cli.get_all(),
+        remove: !cli.get_no_remove(),
+        dry_run: cli.get_dry_run(),
+        yes: cli.get_yes(),
         procedure,
         output,
         input,
     };
 
     match run(config) {
-        Err(error) => {
-            if !quiet {
-                let stderr = std::io::stderr();
-                default_error_handler(&error, &mut stderr.lock());
-            }
-            process::exit(1)
-        }
+        Err(error) => exit_with_error(&error, quiet, code),
         Ok(false) => process::exit(1),
         Ok(true) => process::exit(0),
     }
@@ -58,7 +77,7 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
-    use super::run;
+    use super::{error_code, run};
     use crate::app::Procedure;
     use crate::config::Config;
     use log::LevelFilter;
@@ -80,4 +99,10 @@ mod tests {
             Err(err) => panic!("Failed to run app with defaults:\n{:?}", err),
         }
     }
+
+    #[test]
+    fn diff_errors_exit_apart_from_drift() {
+        assert_eq!(error_code(&Procedure::Diff), 2);
+        assert_eq!(error_code(&Procedure::Install), 1);
+    }
 }
@@ -1,13 +1,32 @@
-use crate::cmd::{pacman::PacmanCmd, PackageManagerCmds};
+use crate::cmd::{
+    apk::ApkCmd, apt::AptCmd, brew::BrewCmd, cargo::CargoCmd, dnf::DnfCmd, flatpak::FlatpakCmd,
+    guix::GuixCmd, nix::NixCmd, npm::NpmCmd, pacman::PacmanCmd, pip::PipUserCmd, pipx::PipxCmd,
+    portage::PortageCmd, snap::SnapCmd, xbps::XbpsCmd, zypper::ZypperCmd, PackageManagerCmds,
+};
 use crate::error::*;
 use std::boxed::Box;
-use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::str::FromStr;
 use std::{env, fs};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ManagerKind {
     Pacman,
+    Apt,
+    Dnf,
+    Zypper,
+    Apk,
+    Xbps,
+    Portage,
+    Flatpak,
+    Snap,
+    Nix,
+    Brew,
+    Cargo,
+    Pipx,
+    Pip,
+    Npm,
+    Guix,
 }
 
 impl Display for ManagerKind {
@@ -22,6 +41,21 @@ impl FromStr for ManagerKind {
     fn from_str(input: &str) -> std::result::Result<ManagerKind, Self::Err> {
         match input.to_lowercase().as_str() {
             "pacman" => Ok(ManagerKind::Pacman),
+            "apt" => Ok(ManagerKind::Apt),
+            "dnf" => Ok(ManagerKind::Dnf),
+            "zypper" => Ok(ManagerKind::Zypper),
+            "apk" => Ok(ManagerKind::Apk),
+            "xbps" => Ok(ManagerKind::Xbps),
+            "portage" => Ok(ManagerKind::Portage),
+            "flatpak" => Ok(ManagerKind::Flatpak),
+            "snap" => Ok(ManagerKind::Snap),
+            "nix" => Ok(ManagerKind::Nix),
+            "brew" => Ok(ManagerKind::Brew),
+            "cargo" => Ok(ManagerKind::Cargo),
+            "pipx" => Ok(ManagerKind::Pipx),
+            "pip" => Ok(ManagerKind::Pip),
+            "npm" => Ok(ManagerKind::Npm),
+            "guix" => Ok(ManagerKind::Guix),
             _ => Err(()),
         }
     }
@@ -29,7 +63,22 @@ impl FromStr for ManagerKind {
 
 fn get_cmd(kind: ManagerKind) -> Box<dyn PackageManagerCmds> {
     match kind {
-        ManagerKind::Pacman => Box::new(PacmanCmd::new()),
+        ManagerKind::Pacman => Box::new(PacmanCmd::new(get_aur_helper(), get_pacman_remove_args())),
+        ManagerKind::Apt => Box::new(AptCmd::new()),
+        ManagerKind::Dnf => Box::new(DnfCmd::new()),
+        ManagerKind::Zypper => Box::new(ZypperCmd::new()),
+        ManagerKind::Apk => Box::new(ApkCmd::new()),
+        ManagerKind::Xbps => Box::new(XbpsCmd::new()),
+        ManagerKind::Portage => Box::new(PortageCmd::new()),
+        ManagerKind::Flatpak => Box::new(FlatpakCmd::new()),
+        ManagerKind::Snap => Box::new(SnapCmd::new()),
+        ManagerKind::Nix => Box::new(NixCmd::new()),
+        ManagerKind::Brew => Box::new(BrewCmd::new()),
+        ManagerKind::Cargo => Box::new(CargoCmd::new()),
+        ManagerKind::Pipx => Box::new(PipxCmd::new()),
+        ManagerKind::Pip => Box::new(PipUserCmd::new()),
+        ManagerKind::Npm => Box::new(NpmCmd::new()),
+        ManagerKind::Guix => Box::new(GuixCmd::new()),
     }
 }
 
@@ -48,13 +97,92 @@ fn is_program_in_path(program: &str) -> bool {
     false
 }
 
+/// AUR helpers that wrap pacman, in order of preference
+const AUR_HELPERS: [&str; 2] = ["paru", "yay"];
+
+fn get_aur_helper() -> Option<&'static str> {
+    let helper = AUR_HELPERS
+        .iter()
+        .copied()
+        .find(|helper| is_program_in_path(helper));
+    if let Some(helper) = helper {
+        debug!("Detected {} AUR helper", helper);
+    }
+    helper
+}
+
+/// Environment variable to override the options pacman removes packages with
+const PACMAN_REMOVE_ENV: &str = "PKGLS_PACMAN_REMOVE";
+
+/// Options pacman removes packages with, including the packages' configuration and
+/// dependencies which aren't needed otherwise
+const PACMAN_REMOVE_ARGS: [&str; 1] = ["-Rns"];
+
+fn get_pacman_remove_args() -> Vec<String> {
+    match env::var(PACMAN_REMOVE_ENV) {
+        Ok(args) if !args.trim().is_empty() => {
+            debug!(
+                "Using pacman remove options from {}={}",
+                PACMAN_REMOVE_ENV, args
+            );
+            args.split_whitespace().map(str::to_string).collect()
+        }
+        _ => PACMAN_REMOVE_ARGS
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect(),
+    }
+}
+
+/// Programs that indicate a manager is available, in order of precedence
+const DETECTED_PROGRAMS: [(ManagerKind, &str); 16] = [
+    (ManagerKind::Pacman, "pacman"),
+    (ManagerKind::Apt, "apt-mark"),
+    (ManagerKind::Dnf, "dnf"),
+    (ManagerKind::Zypper, "zypper"),
+    (ManagerKind::Apk, "apk"),
+    (ManagerKind::Xbps, "xbps-query"),
+    (ManagerKind::Portage, "emerge"),
+    (ManagerKind::Flatpak, "flatpak"),
+    (ManagerKind::Snap, "snap"),
+    (ManagerKind::Nix, "nix-env"),
+    (ManagerKind::Brew, "brew"),
+    (ManagerKind::Cargo, "cargo"),
+    (ManagerKind::Pipx, "pipx"),
+    (ManagerKind::Pip, "pip"),
+    (ManagerKind::Npm, "npm"),
+    (ManagerKind::Guix, "guix"),
+];
+
+/// Determine every manager based on common programs available in $PATH
+fn get_manager_kinds() -> Vec<ManagerKind> {
+    DETECTED_PROGRAMS
+        .iter()
+        .filter(|(_, program)| is_program_in_path(program))
+        .map(|(kind, _)| {
+            debug!(
+                "Detected {} package manager",
+                kind.to_string().to_lowercase()
+            );
+            *kind
+        })
+        .collect()
+}
+
 /// Determine manager based on common programs available in $PATH
 fn get_manager_kind() -> Result<ManagerKind> {
-    if is_program_in_path("pacman") {
-        debug!("Detected {} package manager", "pacman");
-        Ok(ManagerKind::Pacman)
-    } else {
-        Err(ErrorKind::UndetectedManager(env::consts::OS.to_string()).into())
+    match DETECTED_PROGRAMS
+        .iter()
+        .find(|(_, program)| is_program_in_path(program))
+    {
+        Some((kind, _)) => {
+            debug!(
+                "Detected {} package manager",
+                kind.to_string().to_lowercase()
+            );
+            Ok(*kind)
+        }
+        None => Err(ErrorKind::UndetectedManager(env::consts::OS.to_string()).into()),
     }
 }
 
@@ -68,11 +196,25 @@ pub struct Manager {
 impl Manager {
     pub fn new() -> Result<Self> {
         let kind = get_manager_kind()?;
-        let mgr = Self {
+        Ok(Self::from_kind(kind))
+    }
+
+    /// Manager of a kind, whether or not it's detected
+    pub fn from_kind(kind: ManagerKind) -> Self {
+        Self {
             kind,
             cmd: get_cmd(kind),
-        };
-        Ok(mgr)
+        }
+    }
+
+    /// Every manager detected in the environment
+    pub fn all() -> Result<Vec<Self>> {
+        let kinds = get_manager_kinds();
+        if kinds.is_empty() {
+            Err(ErrorKind::UndetectedManager(env::consts::OS.to_string()).into())
+        } else {
+            Ok(kinds.into_iter().map(Self::from_kind).collect())
+        }
     }
 
     /// Set manager kind
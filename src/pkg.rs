@@ -1,6 +1,31 @@
-use crate::cmd::{Installed, PackageList};
+use crate::cmd::{package_names, run_cmds, Installed, Operation, Package, PackageList};
 use crate::error::*;
-use crate::manager::Manager;
+use crate::manager::{Manager, ManagerKind};
+use std::collections::BTreeMap;
+use std::io::{stdin, IsTerminal};
+use std::process::Command;
+
+/// Comparison of a package list with the installed packages
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Diff {
+    /// Packages in the list which aren't installed
+    pub missing: PackageList,
+
+    /// Installed packages which aren't in the list
+    pub extra: PackageList,
+
+    /// Packages in the list which are installed
+    pub present: PackageList,
+}
+
+impl Diff {
+    pub fn has_drift(&self) -> bool {
+        !self.missing.is_empty() || !self.extra.is_empty()
+    }
+}
+
+/// Comparisons for each manager
+pub type Diffs = BTreeMap<ManagerKind, Diff>;
 
 /// Package manager and associated data
 #[derive(Debug)]
@@ -16,26 +41,49 @@ pub struct Pkg {
 }
 
 impl Pkg {
-    fn new() -> Result<Self> {
-        let pkg = Self {
-            manager: Manager::new()?,
+    fn new(manager: Manager) -> Self {
+        Self {
+            manager,
             installed: None,
             missing: None,
-        };
-
-        Ok(pkg)
+        }
     }
 
-    pub fn init() -> Result<Pkg> {
-        let mut pkg = Self::new()?;
+    /// Detected package manager, or the program requested instead
+    ///
+    /// A manager without installed packages is kept so that packages may still be
+    /// installed, only listing them fails
+    pub fn init(program: Option<&str>) -> Result<Pkg> {
+        let mut manager = Manager::new()?;
+        if let Some(program) = program {
+            manager.configure_kind(program)?;
+        }
+        let mut pkg = Self::new(manager);
 
         let installed = pkg.manager.cmd.list_installed()?;
         pkg.set_installed(installed);
+        Ok(pkg)
+    }
 
-        match pkg.installed {
-            Some(_) => Ok(pkg),
-            None => Err(ErrorKind::PackagesNotFound(pkg.manager.get_kind_lowercase()).into()),
-        }
+    /// Every detected package manager
+    ///
+    /// Managers without installed packages are kept so that packages may still be installed
+    pub fn init_all() -> Result<Vec<Pkg>> {
+        let pkgs = Manager::all()?
+            .into_iter()
+            .map(|manager| {
+                let mut pkg = Self::new(manager);
+                if let Err(error) = pkg.update_installed() {
+                    warn!(
+                        "Unable to list installed packages for {}: {}",
+                        pkg.manager.get_kind_lowercase(),
+                        error
+                    );
+                }
+                pkg
+            })
+            .collect();
+        Ok(pkgs)
     }
 
     pub fn update_installed(&mut self) -> Result<()> {
@@ -44,15 +92,15 @@ impl Pkg {
         Ok(())
     }
 
-    fn set_installed(&mut self, installed: Option<Vec<String>>) -> &mut Self {
+    fn set_installed(&mut self, installed: Option<Installed>) -> &mut Self {
         self.installed = installed;
         self
     }
 
-    fn set_missing(&mut self, list: Vec<String>) -> &mut Self {
+    pub fn set_missing(&mut self, list: PackageList) -> &mut Self {
         let missing = if let Some(installed) = self.installed.clone() {
             list.into_iter()
-                .filter(|item| !installed.contains(item))
+                .filter(|item| !installed.iter().any(|p| p.name == item.name))
                 .collect()
         } else {
             list
@@ -61,15 +109,90 @@ impl Pkg {
         self
     }
 
-    pub fn install_missing(&mut self, list: Vec<String>) -> Result<()> {
+    /// Compare a package list with the installed packages by name
+    pub fn diff(&self, list: PackageList) -> Diff {
+        let installed = self.installed.clone().unwrap_or_default();
+        let (present, missing): (PackageList, PackageList) = list
+            .into_iter()
+            .partition(|item| installed.iter().any(|p| p.name == item.name));
+        let extra = installed
+            .into_iter()
+            .filter(|item| !present.iter().any(|p| p.name == item.name))
+            .collect();
+        Diff {
+            missing,
+            extra,
+            present,
+        }
+    }
+
+    /// Commands to install packages, warning about attributes the manager ignores
+    pub fn install_cmds(&self, list: PackageList, yes: bool) -> Result<Vec<Command>> {
+        let attributes = self.manager.cmd.install_attributes();
+        for package in &list {
+            let ignored: Vec<&str> = package
+                .attributes
+                .keys()
+                .map(String::as_str)
+                .filter(|key| !attributes.contains(key))
+                .collect();
+            if !ignored.is_empty() {
+                warn!(
+                    "Ignoring attributes of {} which {} doesn't apply: {}",
+                    package.name,
+                    self.manager.get_kind_lowercase(),
+                    ignored.join(" ")
+                );
+            }
+        }
+        self.manager.cmd.install_cmds(list, yes)
+    }
+
+    /// Fail rather than wait for confirmation which can't be given
+    fn check_confirmation(&self, operation: Operation, yes: bool) -> Result<()> {
+        if !yes && self.manager.cmd.confirms(operation) && !stdin().is_terminal() {
+            Err(ErrorKind::ConfirmationRequired(format!(
+                "{} packages with {}",
+                operation,
+                self.manager.get_kind_lowercase()
+            ))
+            .into())
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn install_missing(&mut self, list: PackageList, yes: bool) -> Result<()> {
         self.set_missing(list.clone());
         match self.missing.clone() {
             Some(missing) if !missing.is_empty() => {
-                debug!("Installing packages: {}", missing.clone().join(" "));
-                match self.manager.cmd.install(missing.clone()) {
+                self.check_confirmation(Operation::Install, yes)?;
+                debug!("Installing packages: {}", package_names(&missing).join(" "));
+                let cmds = self.install_cmds(missing.clone(), yes)?;
+                match run_cmds(cmds) {
                     Ok(_) => {
-                        info!("Successfully installed packages: {}", missing.join(" "));
-                        Ok(())
+                        self.update_installed()?;
+                        self.set_missing(list);
+                        let remaining = self.missing.clone().unwrap_or_default();
+                        let installed: PackageList = missing
+                            .into_iter()
+                            .filter(|item| !remaining.iter().any(|p| p.name == item.name))
+                            .collect();
+                        if !installed.is_empty() {
+                            info!(
+                                "Successfully installed packages: {}",
+                                package_names(&installed).join(" ")
+                            );
+                        }
+                        if remaining.is_empty() {
+                            Ok(())
+                        } else {
+                            Err(ErrorKind::FailedManager(format!(
+                                "Failed to install packages: {}",
+                                package_names(&remaining).join(" ")
+                            ))
+                            .into())
+                        }
                     }
                     Err(_) => {
                         debug!(
@@ -91,7 +214,7 @@ impl Pkg {
                                 );
                                 Err(ErrorKind::FailedManager(format!(
                                     "Failed to install packages: {}",
-                                    missing.join(" ")
+                                    package_names(&missing).join(" ")
                                 ))
                                 .into())
                             }
@@ -110,4 +233,93 @@ impl Pkg {
             }
         }
     }
+
+    /// Installed packages found in a list
+    ///
+    /// The installed packages are kept rather than the listed ones, since they hold
+    /// attributes the manager may need, such as a flatpak's installation
+    pub fn find_installed(&self, list: &[Package]) -> PackageList {
+        self.installed
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|item| list.iter().any(|p| p.name == item.name))
+            .collect()
+    }
+
+    pub fn remove_installed(&mut self, list: PackageList, yes: bool) -> Result<()> {
+        let installed = self.find_installed(&list);
+        if installed.len() < list.len() {
+            let not_installed: PackageList = list
+                .into_iter()
+                .filter(|item| !installed.iter().any(|p| p.name == item.name))
+                .collect();
+            warn!(
+                "Packages aren't installed: {}",
+                package_names(&not_installed).join(" ")
+            );
+        }
+        if installed.is_empty() {
+            warn!("No installed packages found to remove");
+            return Ok(());
+        }
+        self.check_confirmation(Operation::Remove, yes)?;
+        debug!("Removing packages: {}", package_names(&installed).join(" "));
+        let cmds = self.manager.cmd.remove_cmds(installed.clone(), yes)?;
+        match run_cmds(cmds) {
+            Ok(_) => {
+                info!(
+                    "Successfully removed packages: {}",
+                    package_names(&installed).join(" ")
+                );
+                Ok(())
+            }
+            Err(_) => {
+                debug!("Checking installed packages to determine which packages were removed");
+                self.update_installed()?;
+                let remaining = self.find_installed(&installed);
+                let removed: PackageList = installed
+                    .iter()
+                    .filter(|item| !remaining.iter().any(|p| p.name == item.name))
+                    .cloned()
+                    .collect();
+                if !removed.is_empty() {
+                    warn!(
+                        "Removed packages before the failure: {}",
+                        package_names(&removed).join(" ")
+                    );
+                }
+                if remaining.len() == installed.len() {
+                    debug!("No installed packages were removed");
+                    Err(ErrorKind::InterruptedManager("Removal cancelled".into()).into())
+                } else if !remaining.is_empty() {
+                    Err(ErrorKind::FailedManager(format!(
+                        "Failed to remove packages: {}",
+                        package_names(&remaining).join(" ")
+                    ))
+                    .into())
+                } else {
+                    warn!("Cannot find installed packages after the attempted removal");
+                    Err(ErrorKind::InterruptedManager("Removal failed".into()).into())
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pkg;
+    use crate::cmd::Operation;
+    use crate::manager::{Manager, ManagerKind};
+
+    #[test]
+    fn installs_with_pip_without_yes() {
+        let pkg = Pkg {
+            manager: Manager::from_kind(ManagerKind::Pip),
+            installed: Some(vec![]),
+            missing: None,
+        };
+        assert!(pkg.check_confirmation(Operation::Install, false).is_ok());
+    }
 }